@@ -0,0 +1,60 @@
+use crate::day;
+use crate::day::AoCError::LogicError;
+use crate::day::{Answer, AoCError, DayId, Params};
+use crate::report::{Format, Table};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::num::NonZero;
+use std::time::{Duration, Instant};
+
+type Part<'a> = &'a dyn Fn() -> Answer;
+
+/// Average duration of `runs` evaluations of `part`
+fn time(runs: NonZero<usize>, part: Part) -> Result<Duration, AoCError> {
+    let start = Instant::now();
+    for _ in 0..runs.get() {
+        part()?;
+    }
+
+    Ok(start.elapsed() / runs.get() as u32)
+}
+
+pub fn bench(
+    id: DayId,
+    size: NonZero<usize>,
+    runs: NonZero<usize>,
+    seed: Option<u64>,
+    params: &Params,
+) -> Result<(), AoCError> {
+    let seed = seed.unwrap_or_else(rand::random);
+    println!("Benchmarking {id} on a random input of size {size} with seed {seed}");
+    let input = day::generate(id, size.get(), &mut StdRng::seed_from_u64(seed))?;
+    let instance = day::instantiate(id, &input, params)?;
+    let oracle = instance
+        .oracle()
+        .ok_or(LogicError(format!("{id} has no reference implementation")))?;
+    let parts: [(u8, Part, Part); 2] = [
+        (1, &|| instance.part_1(), &|| oracle.reference_part_1()),
+        (2, &|| instance.part_2(), &|| oracle.reference_part_2()),
+    ];
+    let mut table = Table::new(&["part", "solution", "reference", "speedup"]);
+    for (part, solution, reference) in parts {
+        if solution()? != reference()? {
+            return Err(LogicError(format!(
+                "part {part} of {id} disagrees with its reference implementation"
+            )));
+        }
+
+        let solution = time(runs, solution)?;
+        let reference = time(runs, reference)?;
+        table.push(vec![
+            part.to_string(),
+            format!("{solution:?}"),
+            format!("{reference:?}"),
+            format!("{:.1}x", reference.as_secs_f64() / solution.as_secs_f64()),
+        ]);
+    }
+
+    print!("{}", table.render(Format::Table));
+    Ok(())
+}
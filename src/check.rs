@@ -0,0 +1,263 @@
+use crate::day;
+use crate::day::AoCError::{LogicError, ParamError};
+use crate::day::{Answer, AoCError, DayId, Params};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use regex::Regex;
+use std::num::NonZero;
+
+/// Straightforward reference solutions that optimized solvers are cross-checked against
+pub trait Oracle {
+    fn reference_part_1(&self) -> Answer;
+    fn reference_part_2(&self) -> Answer;
+}
+
+#[derive(Debug)]
+struct Mismatch {
+    part: u8,
+    solution: String,
+    reference: String,
+}
+
+fn show(res: Answer) -> String {
+    match res {
+        Ok(val) => val.to_string(),
+        Err(e) => format!("error ({e})"),
+    }
+}
+
+fn find_mismatch(id: DayId, input: &str, params: &Params) -> Result<Option<Mismatch>, AoCError> {
+    // inputs the parser rejects cannot be counterexamples
+    let instance = match day::instantiate(id, input, params) {
+        Ok(instance) => instance,
+        Err(e @ ParamError(_)) => return Err(e),
+        Err(_) => return Ok(None),
+    };
+
+    let oracle = instance
+        .oracle()
+        .ok_or(LogicError(format!("{id} has no reference implementation")))?;
+    let results = [
+        (1, instance.part_1(), oracle.reference_part_1()),
+        (2, instance.part_2(), oracle.reference_part_2()),
+    ];
+    for (part, solution, reference) in results {
+        let agree = match (&solution, &reference) {
+            (Ok(s), Ok(r)) => s == r,
+            (Err(_), Err(_)) => true,
+            _ => false,
+        };
+
+        if !agree {
+            return Ok(Some(Mismatch {
+                part,
+                solution: show(solution),
+                reference: show(reference),
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Smaller variants of the input, most aggressive first: removing chunks of lines, shrinking
+/// numbers and finally removing single characters
+fn shrink_candidates(input: &str) -> Vec<String> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut candidates = Vec::new();
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        for start in (0..lines.len()).step_by(chunk) {
+            let remaining = lines[..start]
+                .iter()
+                .chain(lines[(start + chunk).min(lines.len())..].iter())
+                .map(|l| format!("{l}\n"))
+                .collect::<String>();
+            candidates.push(remaining);
+        }
+
+        chunk /= 2;
+    }
+
+    let number = Regex::new(r"\d+").unwrap();
+    for m in number.find_iter(input) {
+        let Ok(val) = m.as_str().parse::<u128>() else {
+            continue;
+        };
+
+        // decrease by val, val / 2, val / 4, ..., 1 to converge quickly on large numbers
+        let mut delta = val;
+        while delta > 0 {
+            candidates.push(format!(
+                "{}{}{}",
+                &input[..m.start()],
+                val - delta,
+                &input[m.end()..]
+            ));
+            delta /= 2;
+        }
+    }
+
+    for (idx, c) in input.char_indices() {
+        if c != '\n' {
+            candidates.push(format!("{}{}", &input[..idx], &input[idx + c.len_utf8()..]));
+        }
+    }
+
+    candidates
+}
+
+/// Greedily replaces the input by smaller candidates for which `failure` still reports a failure
+pub fn shrink<F>(
+    mut input: String,
+    mut found: F,
+    mut failure: impl FnMut(&str) -> Result<Option<F>, AoCError>,
+) -> Result<(String, F), AoCError> {
+    'outer: loop {
+        for candidate in shrink_candidates(&input) {
+            if let Some(f) = failure(&candidate)? {
+                input = candidate;
+                found = f;
+                continue 'outer;
+            }
+        }
+
+        return Ok((input, found));
+    }
+}
+
+pub fn check(
+    id: DayId,
+    runs: usize,
+    size: NonZero<usize>,
+    seed: Option<u64>,
+    params: &Params,
+) -> Result<(), AoCError> {
+    let seed = seed.unwrap_or_else(rand::random);
+    println!("Checking {id} on {runs} random inputs with seed {seed}");
+    let mut rng = StdRng::seed_from_u64(seed);
+    for run in 1..=runs {
+        let run_size = rng.random_range(1..=size.get());
+        let input = day::generate(id, run_size, &mut rng)?;
+        if let Some(mismatch) = find_mismatch(id, &input, params)? {
+            println!("Mismatch in run {run}, shrinking...");
+            let (input, mismatch) = shrink(input, mismatch, |i| find_mismatch(id, i, params))?;
+            println!(
+                "Minimal counterexample for part {} (solution: {}, reference: {}):\n{input}",
+                mismatch.part, mismatch.solution, mismatch.reference
+            );
+            return Err(LogicError(format!(
+                "{id} disagrees with its reference implementation"
+            )));
+        }
+    }
+
+    println!("All {runs} inputs agree");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query;
+
+    /// Cross-checks a 2025 day on `runs` random inputs of a fixed seed
+    fn check_day(day: u8, runs: usize, params: &[&str]) {
+        let id = DayId {
+            year: 2025,
+            day: NonZero::new(day).unwrap(),
+        };
+        let params = params.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        let params = Params::parse(&params).unwrap();
+        let size = NonZero::new(20).unwrap();
+        check(id, runs, size, Some(2025), &params).unwrap();
+    }
+
+    #[test]
+    fn day1() {
+        check_day(1, 50, &[]);
+        check_day(1, 50, &["positions=7", "start=3", "targets=0,2,5"]);
+    }
+
+    /// The reference scans every id of the ranges, so fewer runs keep the test fast
+    /// The single pass of the `starts` query agrees with evaluating the dial from every start
+    #[test]
+    fn day1_all_starts() {
+        let id = DayId {
+            year: 2025,
+            day: NonZero::new(1).unwrap(),
+        };
+        let mut rng = StdRng::seed_from_u64(2025);
+        for positions in 1..=12 {
+            let input = day::generate(id, 10, &mut rng).unwrap();
+            let dial = [
+                format!("positions={positions}"),
+                format!("targets=0,{}", positions / 2),
+            ];
+            let instantiate = |extra: &[String]| {
+                let params = Params::parse(&[&dial[..], extra].concat()).unwrap();
+                day::instantiate(id, &input, &params).unwrap()
+            };
+            let all_starts = instantiate(&[]);
+            for (part, mode) in [(1, "land"), (2, "pass")] {
+                let runs = query::run(all_starts.querier().unwrap(), &format!("starts {mode}"));
+                let counts = runs
+                    .unwrap()
+                    .split(", ")
+                    .flat_map(|run| {
+                        let (starts, count) = run.split_once(": ").unwrap();
+                        let (first, last) = starts.split_once('-').unwrap_or((starts, starts));
+                        let num_starts =
+                            last.parse::<usize>().unwrap() - first.parse::<usize>().unwrap();
+                        vec![count.to_string(); num_starts + 1]
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(counts.len(), positions);
+                for (start, count) in counts.iter().enumerate() {
+                    let single = instantiate(&[format!("start={start}")]);
+                    let answer = match part {
+                        1 => single.part_1(),
+                        _ => single.part_2(),
+                    };
+                    assert_eq!(
+                        answer.unwrap().to_string(),
+                        *count,
+                        "{positions} positions, start {start}, mode {mode}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn day2() {
+        check_day(2, 5, &[]);
+        check_day(2, 5, &["base=3", "reps=3"]);
+        check_day(2, 5, &["min-reps=3", "max-pattern=2"]);
+    }
+
+    #[test]
+    fn day3() {
+        check_day(3, 50, &[]);
+        check_day(3, 50, &["spacing=3", "forbidden=1,5"]);
+        check_day(3, 50, &["base=16", "batteries-2=30"]);
+    }
+
+    #[test]
+    fn day4() {
+        let von_neumann = ["neighbourhood=von-neumann", "radius=2", "threshold=5"];
+        check_day(4, 50, &[]);
+        check_day(4, 50, &von_neumann);
+        check_day(
+            4,
+            50,
+            &["comparison=ge", "threshold=6", "semantics=synchronous"],
+        );
+        check_day(4, 50, &["wrap=true", "semantics=synchronous"]);
+    }
+
+    #[test]
+    fn day5() {
+        check_day(5, 50, &[]);
+    }
+}
@@ -1,84 +1,208 @@
-use crate::day::AoCError::{DayError, DayNotImplemented};
-use crate::day1::Day1;
-use crate::day2::Day2;
-use crate::day3::Day3;
+use crate::check::Oracle;
+use crate::day::AoCError::{DayError, ParamError, YearNotImplemented};
+use crate::query::Query;
+use crate::report::Report;
+use crate::visualize::Visualize;
+use crate::y2025;
+use num_bigint::BigUint;
+use rand::rngs::StdRng;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 use std::num::NonZero;
+use std::str::FromStr;
 use thiserror::Error;
-use crate::day4::Day4;
-use crate::day5::Day5;
-use crate::day6::Day6;
-use crate::day7::Day7;
-use crate::day8::Day8;
-
-const INPUTS: &[&'static str] = &[
-    include_str!("../inputs/day1.txt"),
-    include_str!("../inputs/day2.txt"),
-    include_str!("../inputs/day3.txt"),
-    include_str!("../inputs/day4.txt"),
-    include_str!("../inputs/day5.txt"),
-    include_str!("../inputs/day6.txt"),
-    include_str!("../inputs/day7.txt"),
-    include_str!("../inputs/day8.txt"),
-];
-const TEST_INPUTS: &[&'static str] = &[
-    include_str!("../tests/day1.txt"),
-    include_str!("../tests/day2.txt"),
-    include_str!("../tests/day3.txt"),
-    include_str!("../tests/day4.txt"),
-    include_str!("../tests/day5.txt"),
-    include_str!("../tests/day6.txt"),
-    include_str!("../tests/day7.txt"),
-    include_str!("../tests/day8.txt"),
-];
-
-const fn min(a: usize, b: usize) -> usize {
-    if a < b { a } else { b }
-}
-
-const N_DAYS: usize = min(INPUTS.len(), TEST_INPUTS.len());
 
 #[derive(Error, Debug)]
 pub enum AoCError {
     #[error("Parse error: {0}")]
     ParseError(String),
-    #[error("Invalid day {0}. Must be in [1, {N_DAYS}]")]
-    DayError(u8),
-    #[error("Day {0} not implemented yet")]
-    DayNotImplemented(u8),
+    #[error("Year {0} not implemented yet")]
+    YearNotImplemented(u16),
+    #[error("Invalid day {1} for year {0}. Must be in [1, {2}]")]
+    DayError(u16, u8, usize),
     #[error("Logic error: {0}")]
-    LogicError(String)
+    LogicError(String),
+    #[error("IO error: {0}")]
+    IoError(String),
+    #[error("Invalid parameter: {0}")]
+    ParamError(String),
 }
 
 pub type Int = u64;
 pub type Res = Result<Int, AoCError>;
+/// Answer to a part, unbounded for days whose results can exceed [`Int`]
+pub type Answer = Result<BigUint, AoCError>;
+pub type DayResult = Result<Box<dyn Day>, AoCError>;
 
 pub trait Day {
-    fn part_1(&self) -> Res;
-    fn part_2(&self) -> Res;
+    fn part_1(&self) -> Answer;
+    fn part_2(&self) -> Answer;
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        None
+    }
+
+    fn oracle(&self) -> Option<&dyn Oracle> {
+        None
+    }
+
+    fn reporter(&self) -> Option<&dyn Report> {
+        None
+    }
+
+    fn querier(&self) -> Option<&dyn Query> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DayId {
+    pub year: u16,
+    pub day: NonZero<u8>,
 }
 
-pub fn instantiate(day: NonZero<u8>, test_instance: bool) -> Result<Box<dyn Day>, AoCError> {
-    let day = day.get();
-    let day_idx = (day - 1) as usize;
-    if day_idx >= INPUTS.len() {
-        return Err(DayError(day));
+impl Display for DayId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
     }
+}
 
-    let data = if test_instance {
-        TEST_INPUTS[day_idx]
-    } else {
-        INPUTS[day_idx]
-    };
+/// Day specific parameters given as `key=value` pairs
+#[derive(Debug, Default)]
+pub struct Params {
+    values: HashMap<String, String>,
+    used: RefCell<HashSet<String>>,
+}
+
+impl Params {
+    pub fn parse(pairs: &[String]) -> Result<Self, AoCError> {
+        let mut values = HashMap::new();
+        for pair in pairs {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or(ParamError(format!("expected key=value, got {pair}")))?;
+            if values.insert(key.to_string(), value.to_string()).is_some() {
+                return Err(ParamError(format!("{key} given more than once")));
+            }
+        }
+
+        Ok(Self {
+            values,
+            used: RefCell::new(HashSet::new()),
+        })
+    }
+
+    pub fn get<T: FromStr<Err: Display>>(&self, key: &str) -> Result<Option<T>, AoCError> {
+        self.used.borrow_mut().insert(key.to_string());
+        self.values
+            .get(key)
+            .map(|v| {
+                v.parse::<T>()
+                    .map_err(|e| ParamError(format!("{key}: {e}")))
+            })
+            .transpose()
+    }
+
+    /// Comma separated list of values
+    pub fn get_list<T: FromStr<Err: Display>>(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<T>>, AoCError> {
+        self.used.borrow_mut().insert(key.to_string());
+        self.values
+            .get(key)
+            .map(|v| {
+                v.split(',')
+                    .map(|item| item.trim().parse::<T>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| ParamError(format!("{key}: {e}")))
+            })
+            .transpose()
+    }
 
-    match day {
-        1 => Ok(Box::new(Day1::new(data)?)),
-        2 => Ok(Box::new(Day2::new(data)?)),
-        3 => Ok(Box::new(Day3::new(data)?)),
-        4 => Ok(Box::new(Day4::new(data)?)),
-        5 => Ok(Box::new(Day5::new(data)?)),
-        6 => Ok(Box::new(Day6::new(data)?)),
-        7 => Ok(Box::new(Day7::new(data)?)),
-        8 => Ok(Box::new(Day8::new(data)?)),
-        _ => Err(DayNotImplemented(day)),
+    fn check_all_used(&self) -> Result<(), AoCError> {
+        let used = self.used.borrow();
+        match self.values.keys().find(|k| !used.contains(*k)) {
+            Some(key) => Err(ParamError(format!("unknown parameter {key}"))),
+            None => Ok(()),
+        }
     }
 }
+
+/// Everything the runner needs to know about a single day of an event
+pub struct DayEntry {
+    pub input: &'static str,
+    pub test_input: &'static str,
+    pub new: fn(&str, &Params) -> DayResult,
+    pub generate: fn(&mut StdRng, usize) -> String,
+}
+
+pub struct Event {
+    pub year: u16,
+    pub days: &'static [DayEntry],
+}
+
+/// Builds an [`Event`] from the day modules of a year. Each `module::Type` needs a
+/// `Type::new(&str)` constructor, a `module::generate` function and input files
+/// `inputs/<year>/<module>.txt` and `tests/<year>/<module>.txt`. Days taking [`Params`] are
+/// listed as `module::Type(params)` and constructed with `Type::with_params(&str, &Params)`
+macro_rules! event {
+    ($year:literal, [$($module:ident::$day:ident$(($params:ident))?),* $(,)?]) => {
+        $crate::day::Event {
+            year: $year,
+            days: &[$($crate::day::DayEntry {
+                input: include_str!(concat!("../inputs/", $year, "/", stringify!($module), ".txt")),
+                test_input: include_str!(concat!("../tests/", $year, "/", stringify!($module), ".txt")),
+                new: |data, _params| {
+                    Ok(Box::new($crate::day::event!(@new $module::$day$(($params))?, data, _params)?))
+                },
+                generate: |rng, size| $module::generate(rng, size),
+            }),*],
+        }
+    };
+    (@new $module:ident::$day:ident, $data:ident, $params:ident) => {
+        $module::$day::new($data)
+    };
+    (@new $module:ident::$day:ident(params), $data:ident, $params:ident) => {
+        $module::$day::with_params($data, $params)
+    };
+}
+pub(crate) use event;
+
+const EVENTS: &[Event] = &[y2025::EVENT];
+
+pub fn latest_year() -> u16 {
+    EVENTS.iter().map(|e| e.year).max().unwrap()
+}
+
+fn entry(id: DayId) -> Result<&'static DayEntry, AoCError> {
+    let event = EVENTS
+        .iter()
+        .find(|e| e.year == id.year)
+        .ok_or(YearNotImplemented(id.year))?;
+    event.days.get(id.day.get() as usize - 1).ok_or(DayError(
+        id.year,
+        id.day.get(),
+        event.days.len(),
+    ))
+}
+
+pub fn builtin_input(id: DayId, test_instance: bool) -> Result<&'static str, AoCError> {
+    let entry = entry(id)?;
+    Ok(if test_instance {
+        entry.test_input
+    } else {
+        entry.input
+    })
+}
+
+pub fn instantiate(id: DayId, data: &str, params: &Params) -> DayResult {
+    let day = (entry(id)?.new)(data, params)?;
+    params.check_all_used()?;
+    Ok(day)
+}
+
+pub fn generate(id: DayId, size: usize, rng: &mut StdRng) -> Result<String, AoCError> {
+    Ok((entry(id)?.generate)(rng, size))
+}
@@ -0,0 +1,174 @@
+use crate::check::shrink;
+use crate::day;
+use crate::day::AoCError::{IoError, LogicError, ParamError};
+use crate::day::{AoCError, DayId, Params};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::num::NonZero;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+const ALPHABET: &[char] = &[
+    '0', '1', '5', '9', ' ', '\n', '\r', ',', '-', '.', '@', '^', 'S', '|', '+', '*', 'R', 'L',
+    'x', 'é', '#', '=',
+];
+
+enum Outcome {
+    Ok,
+    Crash(String),
+    Timeout,
+}
+
+fn case_path() -> PathBuf {
+    env::temp_dir().join(format!("aoc_fuzz_{}.txt", std::process::id()))
+}
+
+/// Runs the day on the input in a child process of this binary, so that panics and aborts can
+/// be observed and hanging inputs can be killed
+fn run_case(
+    id: DayId,
+    input: &str,
+    params: &[String],
+    timeout: Duration,
+) -> Result<Outcome, AoCError> {
+    let io_error = |e: std::io::Error| IoError(e.to_string());
+    let path = case_path();
+    fs::write(&path, input).map_err(io_error)?;
+    let mut child = Command::new(env::current_exe().map_err(io_error)?)
+        .arg(id.day.to_string())
+        .arg("--year")
+        .arg(id.year.to_string())
+        .arg("--input")
+        .arg(&path)
+        .args(params.iter().flat_map(|p| ["--param", p]))
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(io_error)?;
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(io_error)? {
+            break status;
+        }
+
+        if start.elapsed() > timeout {
+            child.kill().map_err(io_error)?;
+            child.wait().map_err(io_error)?;
+            return Ok(Outcome::Timeout);
+        }
+
+        sleep(Duration::from_millis(1));
+    };
+
+    // exit code 1 means the day reported an AoCError
+    if matches!(status.code(), Some(0 | 1)) {
+        return Ok(Outcome::Ok);
+    }
+
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        pipe.read_to_string(&mut stderr).map_err(io_error)?;
+    }
+
+    let panic_msg = stderr
+        .lines()
+        .skip_while(|l| !l.contains("panicked at"))
+        .take(2)
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok(Outcome::Crash(if panic_msg.is_empty() {
+        status.to_string()
+    } else {
+        panic_msg
+    }))
+}
+
+fn mutate(rng: &mut impl Rng, input: &str) -> String {
+    let mut chars = input.chars().collect::<Vec<_>>();
+    for _ in 0..rng.random_range(1..=4) {
+        let pos = rng.random_range(0..=chars.len());
+        let random_char = ALPHABET[rng.random_range(0..ALPHABET.len())];
+        match rng.random_range(0..6) {
+            0 if pos < chars.len() => {
+                chars.remove(pos);
+            }
+            1 => chars.insert(pos, random_char),
+            2 if pos < chars.len() => chars[pos] = random_char,
+            3 => chars.truncate(pos),
+            _ => {
+                let mut lines = chars
+                    .split(|&c| c == '\n')
+                    .map(|l| l.to_vec())
+                    .collect::<Vec<_>>();
+                let line = rng.random_range(0..lines.len());
+                if rng.random_bool(0.5) {
+                    lines.remove(line);
+                } else {
+                    lines.insert(line, lines[line].clone());
+                }
+
+                chars = lines.join(&'\n');
+            }
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+pub fn fuzz(
+    id: DayId,
+    runs: usize,
+    size: NonZero<usize>,
+    seed: Option<u64>,
+    timeout: Duration,
+    params: &[String],
+) -> Result<(), AoCError> {
+    let day_params = Params::parse(params)?;
+    let seed = seed.unwrap_or_else(rand::random);
+    println!("Fuzzing {id} on {runs} mutated inputs with seed {seed}");
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut num_timeouts = 0;
+    let mut crash = None;
+    for run in 1..=runs {
+        let run_size = rng.random_range(1..=size.get());
+        let generated = day::generate(id, run_size, &mut rng)?;
+        if let Err(e @ ParamError(_)) = day::instantiate(id, &generated, &day_params) {
+            return Err(e);
+        }
+
+        let input = mutate(&mut rng, &generated);
+        match run_case(id, &input, params, timeout)? {
+            Outcome::Ok => {}
+            Outcome::Timeout => num_timeouts += 1,
+            Outcome::Crash(msg) => {
+                println!("Crash in run {run}, shrinking...");
+                crash = Some(shrink(input, msg, |i| {
+                    match run_case(id, i, params, timeout)? {
+                        Outcome::Crash(msg) => Ok(Some(msg)),
+                        _ => Ok(None),
+                    }
+                })?);
+                break;
+            }
+        }
+    }
+
+    let _ = fs::remove_file(case_path());
+    if num_timeouts > 0 {
+        println!("{num_timeouts} inputs exceeded the time limit of {timeout:?}");
+    }
+
+    if let Some((input, msg)) = crash {
+        println!("Minimal crashing input ({msg}):\n{input:?}");
+        return Err(LogicError(format!("{id} crashed")));
+    }
+
+    println!("No crashes in {runs} inputs");
+    Ok(())
+}
@@ -1,40 +1,230 @@
+mod bench;
+mod check;
 mod day;
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-
-use crate::day::AoCError;
-use clap::Parser;
+mod fuzz;
+mod query;
+mod report;
+mod visualize;
+mod y2025;
+
+use crate::day::AoCError::{IoError, LogicError};
+use crate::day::{AoCError, DayId, Params};
+use crate::visualize::Settings;
+use clap::{Parser, Subcommand};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::fs;
 use std::num::NonZero;
+use std::path::PathBuf;
 use std::process::exit;
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
-#[command()]
+#[command(subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(required = true)]
-    day: NonZero<u8>,
+    day: Option<NonZero<u8>>,
+
+    /// Year of the event. Defaults to the latest one
+    #[arg(short, long, global = true)]
+    year: Option<u16>,
+
+    /// Day specific parameter
+    #[arg(short, long = "param", value_name = "KEY=VALUE", global = true)]
+    params: Vec<String>,
 
     #[arg(short, long, default_value = "false")]
     test: bool,
+
+    /// Run on the given input file instead of the builtin data
+    #[arg(short, long, conflicts_with = "test")]
+    input: Option<PathBuf>,
+
+    /// Print a detailed report of how the result was obtained
+    #[arg(short, long, conflicts_with = "visualize")]
+    report: Option<report::Format>,
+
+    /// Answer a day specific question about the input, e.g. "position 10". Can be repeated
+    #[arg(short, long = "query", value_name = "QUERY")]
+    queries: Vec<String>,
+
+    /// Render the intermediate states of the solution
+    #[arg(short, long)]
+    visualize: Option<visualize::Mode>,
+
+    /// Delay between two frames in milliseconds
+    #[arg(long, default_value = "100")]
+    delay: u64,
+
+    /// Output file of the report, or directory (frames) / file (gif) of the visualization
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print a random input for a day
+    Generate {
+        day: NonZero<u8>,
+
+        /// Scale of the instance (number of lines, ranges, grid size, ...)
+        #[arg(short, long, default_value = "100")]
+        size: NonZero<usize>,
+
+        /// Seed of the random number generator. Chosen randomly if omitted
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Cross-check solutions against their reference implementations on random inputs
+    Check {
+        day: NonZero<u8>,
+
+        /// Number of random inputs
+        #[arg(short, long, default_value = "100")]
+        runs: usize,
+
+        /// Maximum scale of the random inputs
+        #[arg(short, long, default_value = "20")]
+        size: NonZero<usize>,
+
+        /// Seed of the random number generator. Chosen randomly if omitted
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Time solutions against their reference implementations on a random input
+    Bench {
+        day: NonZero<u8>,
+
+        /// Scale of the random input
+        #[arg(short, long, default_value = "100")]
+        size: NonZero<usize>,
+
+        /// Number of timed repetitions per implementation
+        #[arg(short, long, default_value = "3")]
+        runs: NonZero<usize>,
+
+        /// Seed of the random number generator. Chosen randomly if omitted
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Feed mutated random inputs to a day and report inputs that make it panic
+    Fuzz {
+        day: NonZero<u8>,
+
+        /// Number of mutated inputs
+        #[arg(short, long, default_value = "1000")]
+        runs: usize,
+
+        /// Maximum scale of the random inputs before mutation
+        #[arg(short, long, default_value = "10")]
+        size: NonZero<usize>,
+
+        /// Seed of the random number generator. Chosen randomly if omitted
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Time limit per input in milliseconds
+        #[arg(long, default_value = "5000")]
+        timeout: u64,
+    },
+}
+
+fn generate(id: DayId, size: NonZero<usize>, seed: Option<u64>) -> Result<(), AoCError> {
+    let seed = seed.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let data = day::generate(id, size.get(), &mut rng)?;
+    eprintln!("Generated {id} with seed {seed}");
+    print!("{data}");
+    Ok(())
 }
 
 fn run() -> Result<(), AoCError> {
     let args = Args::parse();
-    let day = day::instantiate(args.day, args.test)?;
-    println!(
-        "Launching day {} on {}",
-        args.day,
-        if args.test {
-            "test data"
-        } else {
-            "the actual data"
+    let year = args.year.unwrap_or_else(day::latest_year);
+    let id = |day| DayId { year, day };
+    match args.command {
+        Some(Command::Generate { day, size, seed }) => return generate(id(day), size, seed),
+        Some(Command::Check {
+            day,
+            runs,
+            size,
+            seed,
+        }) => return check::check(id(day), runs, size, seed, &Params::parse(&args.params)?),
+        Some(Command::Bench {
+            day,
+            size,
+            runs,
+            seed,
+        }) => return bench::bench(id(day), size, runs, seed, &Params::parse(&args.params)?),
+        Some(Command::Fuzz {
+            day,
+            runs,
+            size,
+            seed,
+            timeout,
+        }) => {
+            let timeout = Duration::from_millis(timeout);
+            return fuzz::fuzz(id(day), runs, size, seed, timeout, &args.params);
+        }
+        None => {}
+    }
+
+    let day_id = id(args.day.expect("day is required without subcommand"));
+    let (data, source) = match &args.input {
+        Some(path) => (
+            fs::read_to_string(path).map_err(|e| IoError(format!("{}: {e}", path.display())))?,
+            path.display().to_string(),
+        ),
+        None if args.test => (
+            day::builtin_input(day_id, true)?.to_string(),
+            "test data".to_string(),
+        ),
+        None => (
+            day::builtin_input(day_id, false)?.to_string(),
+            "the actual data".to_string(),
+        ),
+    };
+    let day = day::instantiate(day_id, &data, &Params::parse(&args.params)?)?;
+    println!("Launching {day_id} on {source}");
+    if let Some(format) = args.report {
+        let reporter = day
+            .reporter()
+            .ok_or(LogicError(format!("{day_id} has no report")))?;
+        let report = reporter.report()?.render(format);
+        match &args.output {
+            Some(path) => {
+                fs::write(path, report).map_err(|e| IoError(format!("{}: {e}", path.display())))?
+            }
+            None => print!("{report}"),
+        }
+    }
+
+    if !args.queries.is_empty() {
+        let querier = day
+            .querier()
+            .ok_or(LogicError(format!("{day_id} does not support queries")))?;
+        for q in &args.queries {
+            println!("{q}: {}", query::run(querier, q)?);
         }
-    );
+    }
+
+    if let Some(mode) = args.visualize {
+        let vis = day
+            .visualizer()
+            .ok_or(LogicError(format!("{day_id} cannot be visualized")))?;
+        visualize::render(
+            vis,
+            &Settings {
+                mode,
+                delay: Duration::from_millis(args.delay),
+                output: args.output,
+            },
+        )?;
+    }
+
     println!("Result part 1: {}", day.part_1()?);
     println!("Result part 2: {}", day.part_2()?);
     Ok(())
@@ -0,0 +1,50 @@
+use crate::day::AoCError;
+use crate::day::AoCError::ParamError;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Implemented by days that answer questions about their input beyond the two parts
+pub trait Query {
+    /// Supported queries as (usage, description)
+    fn queries(&self) -> &'static [(&'static str, &'static str)];
+
+    /// Answers the query `name` with its whitespace separated arguments
+    fn query(&self, name: &str, args: &[&str]) -> Result<String, AoCError>;
+}
+
+pub fn run(querier: &dyn Query, query: &str) -> Result<String, AoCError> {
+    let mut words = query.split_whitespace();
+    let name = words.next().unwrap_or_default();
+    let args = words.collect::<Vec<_>>();
+    let known = querier
+        .queries()
+        .iter()
+        .any(|(usage, _)| usage.split_whitespace().next() == Some(name));
+    if !known {
+        let usages = querier
+            .queries()
+            .iter()
+            .map(|(usage, description)| format!("\n  {usage}: {description}"))
+            .collect::<String>();
+        return Err(ParamError(format!(
+            "unknown query '{query}', supported are:{usages}"
+        )));
+    }
+
+    querier.query(name, &args)
+}
+
+/// Parses the argument at `idx`, None if it is missing
+pub fn arg<T: FromStr<Err: Display>>(args: &[&str], idx: usize) -> Result<Option<T>, AoCError> {
+    args.get(idx)
+        .map(|a| {
+            a.parse::<T>()
+                .map_err(|e| ParamError(format!("argument {}: {e}", idx + 1)))
+        })
+        .transpose()
+}
+
+/// Parses the argument at `idx`, failing if it is missing
+pub fn required_arg<T: FromStr<Err: Display>>(args: &[&str], idx: usize) -> Result<T, AoCError> {
+    arg(args, idx)?.ok_or(ParamError(format!("missing argument {}", idx + 1)))
+}
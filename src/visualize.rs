@@ -0,0 +1,157 @@
+use crate::day::AoCError;
+use crate::day::AoCError::IoError;
+use clap::ValueEnum;
+use gif::{Encoder, Frame as GifFrame, Repeat};
+use nalgebra::DMatrix;
+use std::fs;
+use std::fs::File;
+use std::io::{Write, stdout};
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::Duration;
+
+pub type Frame = DMatrix<char>;
+
+/// Implemented by days whose intermediate states can be rendered as a sequence of grids
+pub trait Visualize {
+    fn frames(&self) -> Result<Vec<Frame>, AoCError>;
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum Mode {
+    /// Animate the frames in the terminal
+    Terminal,
+    /// Dump every frame as a numbered text file
+    Frames,
+    /// Write an animated gif
+    Gif,
+}
+
+pub struct Settings {
+    pub mode: Mode,
+    pub delay: Duration,
+    pub output: Option<PathBuf>,
+}
+
+const CELL_SIZE: usize = 4;
+
+/// (cell, ansi colour code, rgb)
+const PALETTE: &[(char, u8, [u8; 3])] = &[
+    ('.', 90, [24, 24, 32]),
+    ('@', 33, [230, 190, 40]),
+    ('x', 31, [200, 50, 50]),
+    ('S', 32, [60, 200, 80]),
+    ('^', 35, [190, 80, 220]),
+    ('|', 36, [80, 200, 230]),
+];
+const DEFAULT_COLOUR: (u8, [u8; 3]) = (37, [220, 220, 220]);
+
+fn palette_index(c: char) -> usize {
+    PALETTE
+        .iter()
+        .position(|&(p, _, _)| p == c)
+        .unwrap_or(PALETTE.len())
+}
+
+fn ansi_colour(c: char) -> u8 {
+    PALETTE
+        .get(palette_index(c))
+        .map_or(DEFAULT_COLOUR.0, |&(_, ansi, _)| ansi)
+}
+
+fn render_terminal(frames: &[Frame], delay: Duration) -> Result<(), AoCError> {
+    let mut out = stdout().lock();
+    for (idx, frame) in frames.iter().enumerate() {
+        let mut buffer = String::from("\x1b[2J\x1b[H");
+        buffer.push_str(&format!("frame {}/{}\n", idx + 1, frames.len()));
+        for row in frame.row_iter() {
+            for &c in row.iter() {
+                buffer.push_str(&format!("\x1b[{}m{}", ansi_colour(c), c));
+            }
+            buffer.push_str("\x1b[0m\n");
+        }
+
+        out.write_all(buffer.as_bytes())
+            .and_then(|_| out.flush())
+            .map_err(|e| IoError(e.to_string()))?;
+        sleep(delay);
+    }
+
+    Ok(())
+}
+
+fn render_text_frames(frames: &[Frame], dir: &Path) -> Result<(), AoCError> {
+    fs::create_dir_all(dir).map_err(|e| IoError(format!("{}: {e}", dir.display())))?;
+    for (idx, frame) in frames.iter().enumerate() {
+        let text = frame
+            .row_iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let path = dir.join(format!("frame_{idx:04}.txt"));
+        fs::write(&path, text + "\n").map_err(|e| IoError(format!("{}: {e}", path.display())))?;
+    }
+
+    println!("Wrote {} frames to {}", frames.len(), dir.display());
+    Ok(())
+}
+
+fn render_gif(frames: &[Frame], delay: Duration, path: &Path) -> Result<(), AoCError> {
+    let first = frames
+        .first()
+        .ok_or(IoError("nothing to render".to_string()))?;
+    let to_u16 = |n: usize| {
+        u16::try_from(n * CELL_SIZE)
+            .map_err(|_| IoError(format!("grid too large for gif: {n} cells")))
+    };
+    let (width, height) = (to_u16(first.ncols())?, to_u16(first.nrows())?);
+    let mut palette: Vec<u8> = PALETTE.iter().flat_map(|&(_, _, rgb)| rgb).collect();
+    palette.extend(DEFAULT_COLOUR.1);
+
+    let gif_error = |e: gif::EncodingError| IoError(format!("{}: {e}", path.display()));
+    let file = File::create(path).map_err(|e| IoError(format!("{}: {e}", path.display())))?;
+    let mut encoder = Encoder::new(file, width, height, &palette).map_err(gif_error)?;
+    encoder.set_repeat(Repeat::Infinite).map_err(gif_error)?;
+    for frame in frames {
+        if frame.shape() != first.shape() {
+            return Err(IoError("frames differ in size".to_string()));
+        }
+
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        for row in frame.row_iter() {
+            let line = row
+                .iter()
+                .flat_map(|&c| [palette_index(c) as u8; CELL_SIZE])
+                .collect::<Vec<_>>();
+            for _ in 0..CELL_SIZE {
+                pixels.extend_from_slice(&line);
+            }
+        }
+
+        let mut gif_frame = GifFrame::from_indexed_pixels(width, height, pixels, None);
+        gif_frame.delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+        encoder.write_frame(&gif_frame).map_err(gif_error)?;
+    }
+
+    println!("Wrote {} frames to {}", frames.len(), path.display());
+    Ok(())
+}
+
+pub fn render(vis: &dyn Visualize, settings: &Settings) -> Result<(), AoCError> {
+    let frames = vis.frames()?;
+    match settings.mode {
+        Mode::Terminal => render_terminal(&frames, settings.delay),
+        Mode::Frames => render_text_frames(
+            &frames,
+            settings.output.as_deref().unwrap_or(Path::new("frames")),
+        ),
+        Mode::Gif => render_gif(
+            &frames,
+            settings.delay,
+            settings
+                .output
+                .as_deref()
+                .unwrap_or(Path::new("visualization.gif")),
+        ),
+    }
+}
@@ -0,0 +1,24 @@
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+
+use crate::day::{Event, event};
+
+pub const EVENT: Event = event!(
+    2025,
+    [
+        day1::Day1(params),
+        day2::Day2(params),
+        day3::Day3(params),
+        day4::Day4(params),
+        day5::Day5,
+        day6::Day6,
+        day7::Day7,
+        day8::Day8,
+    ]
+);
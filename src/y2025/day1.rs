@@ -0,0 +1,621 @@
+use crate::check::Oracle;
+use crate::day::AoCError::{ParamError, ParseError};
+use crate::day::{Answer, AoCError, Day, Int, Params};
+use crate::query::{Query, arg, required_arg};
+use crate::report::{Report, Table};
+use rand::Rng;
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
+
+#[derive(Debug)]
+struct Rotation {
+    line: usize,
+    increasing: bool,
+    steps: u32,
+}
+
+/// Effect of a single rotation on the dial
+#[derive(Debug)]
+struct Step {
+    before: Int,
+    after: Int,
+    /// The rotation ends on a target (part 1)
+    landed: bool,
+    /// Number of clicks ending on a target (part 2)
+    passes: Int,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CountMode {
+    /// Count rotations that end on a target
+    LandOn,
+    /// Count every click that ends on a target
+    PassThrough,
+}
+
+/// Circular dial with positions `0..positions`
+#[derive(Debug, Clone)]
+struct Dial {
+    positions: Int,
+    start: Int,
+    targets: Vec<Int>,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Self {
+            positions: 100,
+            start: 50,
+            targets: vec![0],
+        }
+    }
+}
+
+impl Dial {
+    fn new(positions: Int, start: Int, mut targets: Vec<Int>) -> Result<Self, AoCError> {
+        if positions == 0 || positions > u32::MAX as Int {
+            return Err(ParamError(format!(
+                "the number of positions must be in [1, {}]",
+                u32::MAX
+            )));
+        }
+
+        if let Some(p) = targets.iter().chain([&start]).find(|&&p| p >= positions) {
+            return Err(ParamError(format!(
+                "position {p} is not on a dial with {positions} positions"
+            )));
+        }
+
+        targets.sort_unstable();
+        targets.dedup();
+        Ok(Self {
+            positions,
+            start,
+            targets,
+        })
+    }
+
+    fn is_target(&self, pos: Int) -> bool {
+        self.targets.binary_search(&pos).is_ok()
+    }
+
+    fn turn(&self, pos: Int, r: &Rotation) -> Int {
+        let steps = r.steps as Int % self.positions;
+        if r.increasing {
+            (pos + steps) % self.positions
+        } else {
+            (pos + self.positions - steps) % self.positions
+        }
+    }
+
+    /// Number of clicks of the rotation starting at `pos` that end on `target`
+    fn num_passes(&self, pos: Int, r: &Rotation, target: Int) -> Int {
+        let distance = if r.increasing {
+            (target + self.positions - pos) % self.positions
+        } else {
+            (pos + self.positions - target) % self.positions
+        };
+        // standing on the target does not count, it has to be reached by a full turn
+        let first_hit = if distance == 0 {
+            self.positions
+        } else {
+            distance
+        };
+        match (r.steps as Int).checked_sub(first_hit) {
+            Some(remaining) => remaining / self.positions + 1,
+            None => 0,
+        }
+    }
+
+    fn trace<'a>(&'a self, rotations: &'a [Rotation]) -> impl Iterator<Item = Step> + 'a {
+        rotations.iter().scan(self.start, |pos, r| {
+            let before = *pos;
+            *pos = self.turn(before, r);
+            Some(Step {
+                before,
+                after: *pos,
+                landed: self.is_target(*pos),
+                passes: self
+                    .targets
+                    .iter()
+                    .map(|&t| self.num_passes(before, r, t))
+                    .sum(),
+            })
+        })
+    }
+
+    fn count(&self, rotations: &[Rotation], mode: CountMode) -> Int {
+        self.trace(rotations)
+            .map(|step| match mode {
+                CountMode::LandOn => step.landed as Int,
+                CountMode::PassThrough => step.passes,
+            })
+            .sum()
+    }
+
+    /// Click by click simulation as reference for [`Dial::count`]
+    fn count_by_clicks(&self, rotations: &[Rotation], mode: CountMode) -> Int {
+        let mut n_hits = 0 as Int;
+        let mut pos = self.start;
+        for r in rotations {
+            for _ in 0..r.steps {
+                pos = if r.increasing {
+                    (pos + 1) % self.positions
+                } else {
+                    (pos + self.positions - 1) % self.positions
+                };
+                n_hits += (mode == CountMode::PassThrough && self.is_target(pos)) as Int;
+            }
+
+            n_hits += (mode == CountMode::LandOn && self.is_target(pos)) as Int;
+        }
+
+        n_hits
+    }
+
+    /// Target hits for every start position at once. The rotations only shift the dial, so a
+    /// rotation hits a target from start `s` iff it does from start 0 shifted by `s`
+    fn count_all_starts(&self, rotations: &[Rotation], mode: CountMode) -> Runs {
+        let n = self.positions;
+        let mut hits = Arcs::new(n);
+        let mut offset = 0;
+        for r in rotations {
+            let rem = r.steps as Int % n;
+            let after = self.turn(offset, r);
+            for &t in &self.targets {
+                match mode {
+                    CountMode::LandOn => hits.add((t + n - after) % n, 1),
+                    CountMode::PassThrough => {
+                        hits.base += r.steps as Int / n;
+                        // starts from which the partial turn covers the target
+                        if r.increasing {
+                            hits.add((t + 2 * n - rem - offset) % n, rem);
+                        } else {
+                            hits.add((t + 1 + n - offset) % n, rem);
+                        }
+                    }
+                }
+            }
+
+            offset = after;
+        }
+
+        hits.runs()
+    }
+}
+
+/// Counts per dial position as a sum of arcs, stored sparsely so that large dials stay cheap
+#[derive(Debug)]
+struct Arcs {
+    positions: Int,
+    /// Count shared by all positions
+    base: Int,
+    diff: BTreeMap<Int, i64>,
+}
+
+/// Run-length encoded counts per position as `(first, last, count)`
+type Runs = Vec<(Int, Int, Int)>;
+
+impl Arcs {
+    fn new(positions: Int) -> Self {
+        Self {
+            positions,
+            base: 0,
+            diff: BTreeMap::new(),
+        }
+    }
+
+    /// Increments the counts of the `len` positions starting at `first`, wrapping around
+    fn add(&mut self, first: Int, len: Int) {
+        let n = self.positions;
+        if len == 0 {
+            return;
+        }
+
+        *self.diff.entry(first).or_default() += 1;
+        if first + len <= n {
+            *self.diff.entry(first + len).or_default() -= 1;
+        } else {
+            *self.diff.entry(n).or_default() -= 1;
+            *self.diff.entry(0).or_default() += 1;
+            *self.diff.entry(first + len - n).or_default() -= 1;
+        }
+    }
+
+    fn runs(&self) -> Runs {
+        let mut runs = Runs::new();
+        let mut count = 0;
+        let mut prev = 0;
+        let end = [(self.positions, 0)];
+        for (&pos, &delta) in self.diff.iter().chain(end.iter().map(|(p, d)| (p, d))) {
+            if pos > prev {
+                let total = self.base + count as Int;
+                match runs.last_mut() {
+                    Some((_, last, c)) if *c == total => *last = pos - 1,
+                    _ => runs.push((prev, pos - 1, total)),
+                }
+            }
+            count += delta;
+            prev = pos;
+        }
+
+        runs
+    }
+}
+
+fn show_runs<'a>(runs: impl Iterator<Item = &'a (Int, Int, Int)>, with_count: bool) -> String {
+    let shown = runs
+        .map(|&(first, last, count)| {
+            let range = match first == last {
+                true => first.to_string(),
+                false => format!("{first}-{last}"),
+            };
+            match with_count {
+                true => format!("{range}: {count}"),
+                false => range,
+            }
+        })
+        .collect::<Vec<_>>();
+    match shown.is_empty() {
+        true => "none".to_string(),
+        false => shown.join(", "),
+    }
+}
+
+/// Prefix sums over the rotations, entry `k` describes the state after the first `k` rotations
+#[derive(Debug)]
+struct Analytics {
+    positions: Vec<Int>,
+    landings: Vec<Int>,
+    passes: Vec<Int>,
+    distance: Vec<Int>,
+    /// Number of clicks ending on each position
+    histogram: Runs,
+}
+
+impl Analytics {
+    fn new(dial: &Dial, rotations: &[Rotation]) -> Self {
+        let n = dial.positions;
+        let mut analytics = Self {
+            positions: vec![dial.start],
+            landings: vec![0],
+            passes: vec![0],
+            distance: vec![0],
+            histogram: Runs::new(),
+        };
+        let mut visits = Arcs::new(n);
+        for (r, step) in rotations.iter().zip(dial.trace(rotations)) {
+            let steps = r.steps as Int;
+            visits.base += steps / n;
+            if r.increasing {
+                visits.add((step.before + 1) % n, steps % n);
+            } else {
+                visits.add(step.after, steps % n);
+            }
+
+            analytics.positions.push(step.after);
+            analytics
+                .landings
+                .push(analytics.landings.last().unwrap() + step.landed as Int);
+            analytics
+                .passes
+                .push(analytics.passes.last().unwrap() + step.passes);
+            analytics
+                .distance
+                .push(analytics.distance.last().unwrap() + steps);
+        }
+
+        analytics.histogram = visits.runs();
+        analytics
+    }
+
+    /// Index of the first rotation after which the cumulative count reaches `n`
+    fn nth_hit(cumulative: &[Int], n: Int) -> Option<usize> {
+        let idx = cumulative.partition_point(|&c| c < n);
+        (idx < cumulative.len()).then_some(idx)
+    }
+}
+
+pub struct Day1 {
+    rotations: Vec<Rotation>,
+    dial: Dial,
+    analytics: OnceCell<Analytics>,
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let direction = if rng.random_bool(0.5) { 'R' } else { 'L' };
+            format!("{direction}{}\n", rng.random_range(1..1000))
+        })
+        .collect()
+}
+
+impl Day1 {
+    /// Parses whitespace separated rotations `R<n>` / `L<n>` or signed steps `+<n>` / `-<n>`.
+    /// Everything after a `#` is a comment
+    pub fn new(input: &str) -> Result<Self, AoCError> {
+        let mut rotations = Vec::new();
+        for (l, line) in (1..).zip(input.lines()) {
+            let code = line.split('#').next().unwrap();
+            for token in code.split_whitespace() {
+                let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+                let column = line[..offset].chars().count() + 1;
+                let parse_error = |msg: String| {
+                    ParseError(format!("line {l}, column {column}, '{token}': {msg}"))
+                };
+                let mut chars = token.chars();
+                let increasing = match chars.next().unwrap() {
+                    'R' | '+' => true,
+                    'L' | '-' => false,
+                    c => {
+                        return Err(parse_error(format!(
+                            "invalid direction '{c}', expected R, L, + or -"
+                        )));
+                    }
+                };
+
+                let digits = chars.as_str();
+                if digits.is_empty() {
+                    return Err(parse_error("missing number of steps".to_string()));
+                }
+
+                if let Some(c) = digits.chars().find(|c| !c.is_ascii_digit()) {
+                    return Err(parse_error(format!(
+                        "invalid digit '{c}' in number of steps"
+                    )));
+                }
+
+                let steps = digits.parse::<u32>().map_err(|_| {
+                    parse_error(format!("steps exceed the maximum of {}", u32::MAX))
+                })?;
+                rotations.push(Rotation {
+                    line: l,
+                    increasing,
+                    steps,
+                })
+            }
+        }
+
+        Ok(Self {
+            rotations,
+            dial: Dial::default(),
+            analytics: OnceCell::new(),
+        })
+    }
+
+    /// Supports the parameters `positions`, `start` and `targets` (comma separated) of the dial.
+    /// The start defaults to the middle of the dial, which is 50 for the default dial
+    pub fn with_params(input: &str, params: &Params) -> Result<Self, AoCError> {
+        let default = Dial::default();
+        let positions = params.get("positions")?.unwrap_or(default.positions);
+        let dial = Dial::new(
+            positions,
+            params.get("start")?.unwrap_or(positions / 2),
+            params.get_list("targets")?.unwrap_or(default.targets),
+        )?;
+        Ok(Self {
+            dial,
+            ..Self::new(input)?
+        })
+    }
+}
+
+impl Day for Day1 {
+    fn part_1(&self) -> Answer {
+        Ok(self.dial.count(&self.rotations, CountMode::LandOn).into())
+    }
+
+    fn part_2(&self) -> Answer {
+        Ok(self
+            .dial
+            .count(&self.rotations, CountMode::PassThrough)
+            .into())
+    }
+
+    fn oracle(&self) -> Option<&dyn Oracle> {
+        Some(self)
+    }
+
+    fn reporter(&self) -> Option<&dyn Report> {
+        Some(self)
+    }
+
+    fn querier(&self) -> Option<&dyn Query> {
+        Some(self)
+    }
+}
+
+impl Day1 {
+    fn analytics(&self) -> &Analytics {
+        self.analytics
+            .get_or_init(|| Analytics::new(&self.dial, &self.rotations))
+    }
+
+    fn check_rotation_count(&self, k: usize) -> Result<usize, AoCError> {
+        if k > self.rotations.len() {
+            return Err(ParamError(format!(
+                "there are only {} rotations",
+                self.rotations.len()
+            )));
+        }
+
+        Ok(k)
+    }
+}
+
+/// Optional count mode argument `land` or `pass`, defaulting to the latter
+fn count_mode(args: &[&str], idx: usize) -> Result<CountMode, AoCError> {
+    match arg::<String>(args, idx)?.as_deref() {
+        Some("land") => Ok(CountMode::LandOn),
+        Some("pass") | None => Ok(CountMode::PassThrough),
+        Some(mode) => Err(ParamError(format!("unknown count mode '{mode}'"))),
+    }
+}
+
+impl Query for Day1 {
+    fn queries(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            (
+                "position K",
+                "position of the dial after the first K rotations",
+            ),
+            (
+                "nth-hit N [land|pass]",
+                "first rotation after which a target was hit N times (default: pass)",
+            ),
+            ("histogram", "number of clicks ending on each position"),
+            (
+                "distance [K]",
+                "clicks travelled in the first K rotations (default: all)",
+            ),
+            ("starts [land|pass]", "target hits for every start position"),
+            (
+                "starts-with N [land|pass]",
+                "start positions for which targets are hit N times",
+            ),
+        ]
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> Result<String, AoCError> {
+        let analytics = self.analytics();
+        match name {
+            "position" => {
+                let k = self.check_rotation_count(required_arg(args, 0)?)?;
+                Ok(analytics.positions[k].to_string())
+            }
+            "nth-hit" => {
+                let n = required_arg::<Int>(args, 0)?;
+                let cumulative = match count_mode(args, 1)? {
+                    CountMode::LandOn => &analytics.landings,
+                    CountMode::PassThrough => &analytics.passes,
+                };
+                Ok(match Analytics::nth_hit(cumulative, n) {
+                    Some(0) => "before the first rotation".to_string(),
+                    Some(k) => format!("rotation {k} (line {})", self.rotations[k - 1].line),
+                    None => format!(
+                        "never, targets are hit {} times",
+                        cumulative.last().unwrap()
+                    ),
+                })
+            }
+            "histogram" => Ok(show_runs(analytics.histogram.iter(), true)),
+            "distance" => Ok(analytics.distance
+                [self.check_rotation_count(arg(args, 0)?.unwrap_or(self.rotations.len()))?]
+            .to_string()),
+            "starts" => {
+                let counts = self
+                    .dial
+                    .count_all_starts(&self.rotations, count_mode(args, 0)?);
+                Ok(show_runs(counts.iter(), true))
+            }
+            "starts-with" => {
+                let n = required_arg::<Int>(args, 0)?;
+                let counts = self
+                    .dial
+                    .count_all_starts(&self.rotations, count_mode(args, 1)?);
+                Ok(show_runs(counts.iter().filter(|run| run.2 == n), false))
+            }
+            _ => Err(ParamError(format!("unknown query {name}"))),
+        }
+    }
+}
+
+impl Report for Day1 {
+    fn report(&self) -> Result<Table, AoCError> {
+        let mut table = Table::new(&[
+            "line",
+            "direction",
+            "steps",
+            "before",
+            "after",
+            "hits part 1",
+            "hits part 2",
+        ]);
+        for (r, step) in self.rotations.iter().zip(self.dial.trace(&self.rotations)) {
+            table.push(vec![
+                r.line.to_string(),
+                if r.increasing { "R" } else { "L" }.to_string(),
+                r.steps.to_string(),
+                step.before.to_string(),
+                step.after.to_string(),
+                (step.landed as Int).to_string(),
+                step.passes.to_string(),
+            ]);
+        }
+
+        Ok(table)
+    }
+}
+
+impl Oracle for Day1 {
+    fn reference_part_1(&self) -> Answer {
+        Ok(self
+            .dial
+            .count_by_clicks(&self.rotations, CountMode::LandOn)
+            .into())
+    }
+
+    fn reference_part_2(&self) -> Answer {
+        Ok(self
+            .dial
+            .count_by_clicks(&self.rotations, CountMode::PassThrough)
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Vec<(usize, bool, u32)> {
+        Day1::new(input)
+            .unwrap()
+            .rotations
+            .iter()
+            .map(|r| (r.line, r.increasing, r.steps))
+            .collect()
+    }
+
+    fn parse_error(input: &str) -> String {
+        match Day1::new(input) {
+            Err(ParseError(msg)) => msg,
+            Err(e) => panic!("unexpected error {e}"),
+            Ok(_) => panic!("{input:?} parsed"),
+        }
+    }
+
+    #[test]
+    fn signed_steps_and_comments() {
+        let input = "R10 -5 # L3\n\n  +7\tL2\n# R1\n";
+        let expected = [(1, true, 10), (1, false, 5), (3, true, 7), (3, false, 2)];
+        assert_eq!(parse(input), expected);
+        assert_eq!(parse("L68 R30"), parse("-68 +30"));
+    }
+
+    #[test]
+    fn errors_point_at_the_token() {
+        assert_eq!(
+            parse_error("R5\n  x7"),
+            "line 2, column 3, 'x7': invalid direction 'x', expected R, L, + or -"
+        );
+        // columns count characters, not bytes
+        assert_eq!(
+            parse_error("R1\u{a0}L"),
+            "line 1, column 4, 'L': missing number of steps"
+        );
+        assert_eq!(
+            parse_error("R1 R1x"),
+            "line 1, column 4, 'R1x': invalid digit 'x' in number of steps"
+        );
+    }
+
+    #[test]
+    fn steps_overflowing_u32() {
+        assert_eq!(parse("R4294967295"), [(1, true, u32::MAX)]);
+        assert_eq!(
+            parse_error("L4294967296"),
+            "line 1, column 1, 'L4294967296': steps exceed the maximum of 4294967295"
+        );
+    }
+}
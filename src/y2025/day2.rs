@@ -0,0 +1,422 @@
+use crate::check::Oracle;
+use crate::day::AoCError::{LogicError, ParamError, ParseError};
+use crate::day::{Answer, AoCError, Day, Params};
+use crate::report::{Report, Table};
+use num_bigint::{BigInt, BigUint};
+use rand::Rng;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::num::IntErrorKind;
+
+/// Ids are parsed as 128 bit numbers, wider bounds are rejected
+type Id = u128;
+
+/// Invalid ids listed per range in the report, the rest are only counted
+const MAX_REPORTED_IDS: usize = 10;
+
+#[derive(Debug)]
+struct IdRange {
+    start: Id,
+    end: Id,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Repetitions {
+    Exactly(u32),
+    AtLeast(u32),
+}
+
+/// Predicate for invalid ids: their digits in `base` are a repeated pattern
+#[derive(Debug, Clone, Copy)]
+struct Pattern {
+    base: u32,
+    repetitions: Repetitions,
+    max_len: u32,
+}
+
+impl Pattern {
+    fn new(base: u32, repetitions: Repetitions, max_len: u32) -> Result<Self, AoCError> {
+        if !(2..=36).contains(&base) {
+            return Err(ParamError(format!("base {base} is not in [2, 36]")));
+        }
+
+        let (Repetitions::Exactly(k) | Repetitions::AtLeast(k)) = repetitions;
+        if k < 2 {
+            return Err(ParamError(format!(
+                "a pattern has to be repeated at least twice, got {k}"
+            )));
+        }
+
+        if max_len == 0 {
+            return Err(ParamError(
+                "the maximum pattern length must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            base,
+            repetitions,
+            max_len,
+        })
+    }
+
+    fn num_digits(&self, n: Id) -> u32 {
+        n.checked_ilog(self.base as Id).unwrap_or(0) + 1
+    }
+
+    /// Whether ids with `len` digits made of a pattern with `period` digits are invalid
+    fn allows(&self, len: u32, period: u32) -> bool {
+        let reps = len / period;
+        period <= self.max_len
+            && len.is_multiple_of(period)
+            && match self.repetitions {
+                Repetitions::Exactly(k) => reps == k,
+                Repetitions::AtLeast(k) => reps >= k,
+            }
+    }
+
+    /// Periods of the invalid ids with `len` digits, each with the sign it contributes with to
+    /// the inclusion–exclusion sum
+    fn periods(&self, len: u32) -> Vec<(u32, i128)> {
+        let allowed = (1..len)
+            .filter(|&p| self.allows(len, p))
+            .collect::<Vec<_>>();
+        if let Repetitions::Exactly(_) = self.repetitions {
+            return allowed.into_iter().map(|p| (p, 1)).collect();
+        }
+
+        // the allowed periods are closed under taking divisors, so every invalid id has its
+        // minimal period `d` among them. Counting each id once via its minimal period means
+        // weighting the ids with period `e` by the Möbius sum over the allowed multiples `d`
+        allowed
+            .iter()
+            .map(|&e| {
+                let coefficient = allowed
+                    .iter()
+                    .filter(|&&d| d.is_multiple_of(e))
+                    .map(|&d| mobius(d / e))
+                    .sum();
+                (e, coefficient)
+            })
+            .filter(|&(_, coefficient)| coefficient != 0)
+            .collect()
+    }
+
+    /// Digits of `id`, least significant first
+    fn digits(&self, id: Id) -> Vec<u32> {
+        let mut digits = Vec::new();
+        let mut rest = id;
+        loop {
+            digits.push((rest % self.base as Id) as u32);
+            rest /= self.base as Id;
+            if rest == 0 {
+                return digits;
+            }
+        }
+    }
+
+    /// Digit by digit check as reference for [`Pattern::periods`]
+    fn matches(&self, id: Id) -> bool {
+        let digits = self.digits(id);
+        let len = digits.len();
+        (1..len).any(|p| {
+            self.allows(len as u32, p as u32) && (p..len).all(|i| digits[i] == digits[i - p])
+        })
+    }
+
+    /// Splits `range` into the sub ranges `(len, lo, hi)` of ids with `len` digits
+    fn by_length(&self, range: &IdRange) -> impl Iterator<Item = (u32, Id, Id)> {
+        let base = self.base as Id;
+        let lengths = self.num_digits(range.start)..=self.num_digits(range.end);
+        // reversed ranges are empty
+        lengths
+            .filter(move |_| range.start <= range.end)
+            .map(move |len| {
+                let lo = range.start.max(base.pow(len - 1));
+                let hi = range
+                    .end
+                    .min(base.checked_pow(len).map_or(Id::MAX, |p| p - 1));
+                (len, lo, hi)
+            })
+    }
+
+    /// Multiplier and range of the `period` digit patterns whose repetitions are the `len`
+    /// digit ids in `lo..=hi`, None if there are none. These ids are exactly the multiples
+    /// `x * m` of the repunit-like multiplier `m = 10..010..01` (in `base`) with a `period` digit
+    /// pattern `x`
+    fn patterns(&self, lo: Id, hi: Id, len: u32, period: u32) -> Option<(Id, Id, Id)> {
+        let base = self.base as Id;
+        let shift = base.pow(period);
+        // a multiplier that overflows is larger than any id
+        let multiplier =
+            (0..len / period).try_fold(0 as Id, |m, _| m.checked_mul(shift)?.checked_add(1))?;
+        let first = lo.div_ceil(multiplier).max(base.pow(period - 1));
+        let last = (hi / multiplier).min(shift - 1);
+        (first <= last).then_some((multiplier, first, last))
+    }
+
+    /// The `limit` smallest invalid ids in `range`, each with the length of its shortest
+    /// allowed pattern. The ids of each period ascend, so only their first `limit` can be among
+    /// the smallest
+    fn first_invalid_ids(&self, range: &IdRange, limit: usize) -> BTreeMap<Id, u32> {
+        let mut ids = BTreeMap::new();
+        for (len, lo, hi) in self.by_length(range) {
+            if ids.len() >= limit {
+                break;
+            }
+
+            for period in (1..len).filter(|&p| self.allows(len, p)) {
+                if let Some((multiplier, first, last)) = self.patterns(lo, hi, len, period) {
+                    for x in (first..=last).take(limit) {
+                        ids.entry(x * multiplier).or_insert(period);
+                    }
+                }
+            }
+        }
+
+        ids.into_iter().take(limit).collect()
+    }
+
+    /// Number of invalid ids in `range`, by the same inclusion–exclusion as their sum
+    fn count_invalid(&self, range: &IdRange) -> BigUint {
+        let mut count = BigInt::ZERO;
+        for (len, lo, hi) in self.by_length(range) {
+            for (period, sign) in self.periods(len) {
+                if let Some((_, first, last)) = self.patterns(lo, hi, len, period) {
+                    count += BigInt::from(last - first + 1) * sign;
+                }
+            }
+        }
+
+        count.to_biguint().expect("ids are counted at least once")
+    }
+
+    /// `id` written as `pattern x repetitions`
+    fn show(&self, id: Id, period: u32) -> String {
+        let digits = self.digits(id);
+        let pattern = digits
+            .iter()
+            .rev()
+            .take(period as usize)
+            .map(|&d| char::from_digit(d, self.base).unwrap())
+            .collect::<String>();
+        format!("{id} ({pattern} x {})", digits.len() as u32 / period)
+    }
+
+    /// Sum of the `len` digit ids in `lo..=hi` consisting of a repeated pattern of `period`
+    /// digits, an arithmetic series over the patterns. It can exceed 128 bits
+    fn sum_with_period(&self, lo: Id, hi: Id, len: u32, period: u32) -> BigUint {
+        match self.patterns(lo, hi, len, period) {
+            Some((multiplier, first, last)) => {
+                BigUint::from(last - first + 1) * (BigUint::from(first) + last) / 2u32 * multiplier
+            }
+            None => BigUint::ZERO,
+        }
+    }
+}
+
+pub struct Day2 {
+    ids: Vec<IdRange>,
+    part_1: Pattern,
+    part_2: Pattern,
+}
+
+fn mobius(mut n: u32) -> i128 {
+    let mut sign = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+
+            sign = -sign;
+        }
+
+        p += 1;
+    }
+
+    if n > 1 { -sign } else { sign }
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let ranges = (0..size)
+        .map(|_| {
+            // mostly ids of the size in the puzzle, some beyond 64 bits
+            let digits = match rng.random_bool(0.8) {
+                true => rng.random_range(1..=10),
+                false => rng.random_range(11..=30),
+            };
+            let start = rng.random_range(10u128.pow(digits - 1)..10u128.pow(digits));
+            let end = start + rng.random_range(0..10u128.pow(digits.min(5)));
+            format!("{start}-{end}")
+        })
+        .collect::<Vec<_>>();
+    ranges.join(",") + "\n"
+}
+
+impl Day2 {
+    pub fn new(input: &str) -> Result<Self, AoCError> {
+        let re = Regex::new(r"((?<s>\d+)-(?<e>\d+))").unwrap();
+        let ranges: Vec<&str> = input.split(',').collect();
+        let mut ids = Vec::with_capacity(ranges.len());
+        for (idx, idr) in (1..).zip(ranges) {
+            let parse_error = |msg| ParseError(format!("range {idx} invalid: {msg}"));
+            let range = re
+                .captures(idr)
+                .ok_or(parse_error("no regex match".to_string()))?;
+            let bound = |digits: &str| {
+                digits.parse::<Id>().map_err(|e| match e.kind() {
+                    IntErrorKind::PosOverflow => parse_error(format!(
+                        "bound {digits} exceeds the supported maximum of {}",
+                        Id::MAX
+                    )),
+                    _ => parse_error(e.to_string()),
+                })
+            };
+            ids.push(IdRange {
+                start: bound(&range["s"])?,
+                end: bound(&range["e"])?,
+            });
+        }
+
+        Ok(Self {
+            ids,
+            part_1: Pattern::new(10, Repetitions::Exactly(2), u32::MAX)?,
+            part_2: Pattern::new(10, Repetitions::AtLeast(2), u32::MAX)?,
+        })
+    }
+
+    /// Supports the parameters `base` of the ids, `reps` (exact repetitions in part 1),
+    /// `min-reps` (minimum repetitions in part 2) and `max-pattern` (maximum pattern length)
+    pub fn with_params(input: &str, params: &Params) -> Result<Self, AoCError> {
+        let base = params.get("base")?.unwrap_or(10);
+        let max_len = params.get("max-pattern")?.unwrap_or(u32::MAX);
+        let reps = params.get("reps")?.unwrap_or(2);
+        let min_reps = params.get("min-reps")?.unwrap_or(2);
+        Ok(Self {
+            part_1: Pattern::new(base, Repetitions::Exactly(reps), max_len)?,
+            part_2: Pattern::new(base, Repetitions::AtLeast(min_reps), max_len)?,
+            ..Self::new(input)?
+        })
+    }
+
+    fn calc_sum_invalid(&self, pattern: &Pattern) -> Answer {
+        let mut sum_invalid = BigInt::ZERO;
+        for id_range in &self.ids {
+            for (len, lo, hi) in pattern.by_length(id_range) {
+                for (period, sign) in pattern.periods(len) {
+                    sum_invalid +=
+                        BigInt::from(pattern.sum_with_period(lo, hi, len, period)) * sign;
+                }
+            }
+        }
+
+        sum_invalid
+            .to_biguint()
+            .ok_or(LogicError("negative sum of invalid ids".to_string()))
+    }
+
+    /// Checks every id of every range as reference for [`Day2::calc_sum_invalid`]
+    fn calc_sum_invalid_by_scan(&self, pattern: &Pattern) -> Answer {
+        let mut sum_invalid = BigUint::ZERO;
+        for id_range in &self.ids {
+            for id in id_range.start..=id_range.end {
+                if pattern.matches(id) {
+                    sum_invalid += id;
+                }
+            }
+        }
+
+        Ok(sum_invalid)
+    }
+}
+
+impl Day for Day2 {
+    fn part_1(&self) -> Answer {
+        self.calc_sum_invalid(&self.part_1)
+    }
+
+    fn part_2(&self) -> Answer {
+        self.calc_sum_invalid(&self.part_2)
+    }
+
+    fn oracle(&self) -> Option<&dyn Oracle> {
+        Some(self)
+    }
+
+    fn reporter(&self) -> Option<&dyn Report> {
+        Some(self)
+    }
+}
+
+impl Report for Day2 {
+    fn report(&self) -> Result<Table, AoCError> {
+        let mut table = Table::new(&[
+            "range",
+            "start",
+            "end",
+            "part",
+            "count",
+            "invalid ids",
+            "notes",
+        ]);
+        for (idx, id_range) in (1..).zip(&self.ids) {
+            let mut notes = Vec::new();
+            if id_range.start > id_range.end {
+                notes.push("reversed, treated as empty".to_string());
+            }
+
+            let overlapping = (1..)
+                .zip(&self.ids)
+                .filter(|&(other, r)| {
+                    other != idx
+                        && r.start <= r.end
+                        && id_range.start <= id_range.end
+                        && r.start <= id_range.end
+                        && id_range.start <= r.end
+                })
+                .map(|(other, _)| other.to_string())
+                .collect::<Vec<_>>();
+            if !overlapping.is_empty() {
+                notes.push(format!("overlaps {}", overlapping.join(", ")));
+            }
+
+            for (part, pattern) in [(1, &self.part_1), (2, &self.part_2)] {
+                let count = pattern.count_invalid(id_range);
+                let shown = pattern.first_invalid_ids(id_range, MAX_REPORTED_IDS);
+                let mut invalid = shown
+                    .iter()
+                    .map(|(&id, &period)| pattern.show(id, period))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if count > BigUint::from(shown.len()) {
+                    invalid.push_str(&format!(" and {} more", count.clone() - shown.len()));
+                }
+
+                table.push(vec![
+                    idx.to_string(),
+                    id_range.start.to_string(),
+                    id_range.end.to_string(),
+                    part.to_string(),
+                    count.to_string(),
+                    invalid,
+                    notes.join("; "),
+                ]);
+            }
+        }
+
+        Ok(table)
+    }
+}
+
+impl Oracle for Day2 {
+    fn reference_part_1(&self) -> Answer {
+        self.calc_sum_invalid_by_scan(&self.part_1)
+    }
+
+    fn reference_part_2(&self) -> Answer {
+        self.calc_sum_invalid_by_scan(&self.part_2)
+    }
+}
@@ -0,0 +1,366 @@
+use crate::check::Oracle;
+use crate::day::AoCError::{LogicError, ParamError, ParseError};
+use crate::day::{Answer, AoCError, Day, Params};
+use crate::report::{Report, Table};
+use num_bigint::BigUint;
+use rand::Rng;
+
+/// Restrictions on which batteries can be chosen together
+#[derive(Debug, Clone)]
+struct Constraints {
+    /// Minimum distance between the positions of two chosen batteries, 2 forbids adjacent ones
+    spacing: usize,
+    /// Positions that cannot be chosen
+    forbidden: Vec<usize>,
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Self {
+            spacing: 1,
+            forbidden: Vec::new(),
+        }
+    }
+}
+
+impl Constraints {
+    fn new(spacing: usize, mut forbidden: Vec<usize>) -> Result<Self, AoCError> {
+        if spacing == 0 {
+            return Err(ParamError("the spacing must be at least 1".to_string()));
+        }
+
+        forbidden.sort_unstable();
+        forbidden.dedup();
+        Ok(Self { spacing, forbidden })
+    }
+
+    fn is_trivial(&self) -> bool {
+        self.spacing == 1 && self.forbidden.is_empty()
+    }
+
+    /// Spacing for a bank of `len` batteries, any spacing beyond `len` allows a single battery
+    fn spacing(&self, len: usize) -> usize {
+        self.spacing.min(len + 1)
+    }
+
+    fn allows(&self, idx: usize) -> bool {
+        self.forbidden.binary_search(&idx).is_err()
+    }
+}
+
+pub struct Day3 {
+    banks: Vec<Vec<u8>>,
+    /// Base in which the batteries are digits of the joltage
+    base: u32,
+    /// Number of batteries to choose in each part
+    num_batteries: [usize; 2],
+    constraints: Constraints,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Goal {
+    Largest,
+    Smallest,
+}
+
+/// Indices of the `num_batteries` batteries forming the best number in linear time. The chosen
+/// batteries are kept on a monotonic stack from which a better battery evicts its predecessors
+/// as long as enough batteries remain to fill the selection
+fn select(bank: &[u8], num_batteries: usize, goal: Goal) -> Result<Vec<usize>, AoCError> {
+    if bank.len() < num_batteries {
+        return Err(LogicError(format!(
+            "cannot chose {num_batteries} batteries from bank of size {}",
+            bank.len()
+        )));
+    }
+
+    let better = |a: u8, b: u8| match goal {
+        Goal::Largest => a > b,
+        Goal::Smallest => a < b,
+    };
+    let mut num_droppable = bank.len() - num_batteries;
+    let mut chosen: Vec<usize> = Vec::with_capacity(num_batteries);
+    for (idx, &battery) in bank.iter().enumerate() {
+        while num_droppable > 0 && chosen.last().is_some_and(|&top| better(battery, bank[top])) {
+            chosen.pop();
+            num_droppable -= 1;
+        }
+
+        if chosen.len() < num_batteries {
+            chosen.push(idx);
+        } else {
+            num_droppable -= 1;
+        }
+    }
+
+    Ok(chosen)
+}
+
+/// Selection under constraints. `available[i]` is the maximum number of batteries that can be
+/// chosen from the positions `i..`, which makes every greedy choice checkable in constant time
+fn select_constrained(
+    bank: &[u8],
+    num_batteries: usize,
+    goal: Goal,
+    constraints: &Constraints,
+) -> Result<Vec<usize>, AoCError> {
+    let spacing = constraints.spacing(bank.len());
+    let mut available = vec![0; bank.len() + spacing];
+    for idx in (0..bank.len()).rev() {
+        available[idx] = available[idx + 1];
+        if constraints.allows(idx) {
+            available[idx] = available[idx].max(available[idx + spacing] + 1);
+        }
+    }
+
+    if available[0] < num_batteries {
+        return Err(LogicError(format!(
+            "cannot chose {num_batteries} batteries from bank of size {} with the given constraints",
+            bank.len()
+        )));
+    }
+
+    let better = |a: u8, b: u8| match goal {
+        Goal::Largest => a > b,
+        Goal::Smallest => a < b,
+    };
+    let mut chosen: Vec<usize> = Vec::with_capacity(num_batteries);
+    let mut first = 0;
+    for remaining in (0..num_batteries).rev() {
+        let mut best: Option<usize> = None;
+        for idx in first..bank.len() {
+            if available[idx] <= remaining {
+                break;
+            }
+
+            if constraints.allows(idx)
+                && available[idx + spacing] >= remaining
+                && best.is_none_or(|b| better(bank[idx], bank[b]))
+            {
+                best = Some(idx);
+            }
+        }
+
+        let idx = best.expect("a feasible battery exists while batteries are available");
+        chosen.push(idx);
+        first = idx + spacing;
+    }
+
+    Ok(chosen)
+}
+
+/// Joltage of the chosen batteries, arbitrarily many digits long
+fn value(bank: &[u8], chosen: &[usize], base: u32) -> BigUint {
+    let digits = chosen.iter().map(|&idx| bank[idx]).collect::<Vec<_>>();
+    BigUint::from_radix_be(&digits, base).expect("batteries are digits")
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut bank = (0..100)
+                .map(|_| char::from(b'0' + rng.random_range(1..=9)))
+                .collect::<String>();
+            bank.push('\n');
+            bank
+        })
+        .collect()
+}
+
+/// Dynamic program over (position, batteries left) instead of the greedy choice
+fn joltage_reference(
+    bank: &[u8],
+    num_batteries: usize,
+    constraints: &Constraints,
+    base: u32,
+) -> Result<BigUint, AoCError> {
+    // best[i][j] = digits of the largest number built from j batteries at the positions i..,
+    // numbers of equal length compare like their digit sequences
+    let spacing = constraints.spacing(bank.len());
+    let mut best: Vec<Vec<Option<Vec<u8>>>> =
+        vec![vec![None; num_batteries + 1]; bank.len() + spacing];
+    for row in &mut best {
+        row[0] = Some(Vec::new());
+    }
+
+    for idx in (0..bank.len()).rev() {
+        for j in 1..=num_batteries {
+            best[idx][j] = best[idx + 1][j].clone();
+            if !constraints.allows(idx) {
+                continue;
+            }
+
+            if let Some(rest) = &best[idx + spacing][j - 1] {
+                let candidate = [&[bank[idx]], rest.as_slice()].concat();
+                if best[idx][j].as_ref().is_none_or(|b| candidate > *b) {
+                    best[idx][j] = Some(candidate);
+                }
+            }
+        }
+    }
+
+    let digits = best[0][num_batteries].take().ok_or(LogicError(format!(
+        "cannot chose {num_batteries} batteries from bank of size {} with the given constraints",
+        bank.len()
+    )))?;
+    Ok(BigUint::from_radix_be(&digits, base).expect("batteries are digits"))
+}
+
+impl Day3 {
+    pub fn new(data: &str) -> Result<Self, AoCError> {
+        Self::parse(data, 10)
+    }
+
+    fn parse(data: &str, base: u32) -> Result<Self, AoCError> {
+        if !(2..=36).contains(&base) {
+            return Err(ParamError(format!("base {base} is not in [2, 36]")));
+        }
+
+        let lines: Vec<&str> = data.lines().collect();
+        let mut banks: Vec<Vec<u8>> = Vec::with_capacity(lines.len());
+        for (l, line) in (1..).zip(lines) {
+            let parse_error = |msg| ParseError(format!("error in line {l}: {msg}"));
+            let bank: Result<Vec<u8>, _> = line
+                .chars()
+                .enumerate()
+                .map(|(idx, c)| {
+                    c.to_digit(base)
+                        .ok_or(parse_error(format!(
+                            "position {idx} is not a digit in base {base}"
+                        )))
+                        .map(|d| d as u8)
+                })
+                .collect();
+            banks.push(bank?)
+        }
+
+        Ok(Self {
+            banks,
+            base,
+            num_batteries: [2, 12],
+            constraints: Constraints::default(),
+        })
+    }
+
+    /// Supports the parameters `base` of the banks, `batteries-1` and `batteries-2` (number of
+    /// batteries chosen in each part), `spacing` (minimum distance between chosen batteries) and
+    /// `forbidden` (comma separated positions that cannot be chosen, counted from 0)
+    pub fn with_params(data: &str, params: &Params) -> Result<Self, AoCError> {
+        let day = match params.get("base")? {
+            Some(base) => Self::parse(data, base)?,
+            None => Self::new(data)?,
+        };
+        let default = Constraints::default();
+        Ok(Self {
+            num_batteries: [
+                params.get("batteries-1")?.unwrap_or(day.num_batteries[0]),
+                params.get("batteries-2")?.unwrap_or(day.num_batteries[1]),
+            ],
+            constraints: Constraints::new(
+                params.get("spacing")?.unwrap_or(default.spacing),
+                params.get_list("forbidden")?.unwrap_or(default.forbidden),
+            )?,
+            ..day
+        })
+    }
+
+    fn select(
+        &self,
+        bank: &[u8],
+        num_batteries: usize,
+        goal: Goal,
+    ) -> Result<Vec<usize>, AoCError> {
+        if self.constraints.is_trivial() {
+            select(bank, num_batteries, goal)
+        } else {
+            select_constrained(bank, num_batteries, goal, &self.constraints)
+        }
+    }
+
+    fn sum_joltage_reference(&self, num_batteries: usize) -> Answer {
+        self.banks
+            .iter()
+            .map(|bank| joltage_reference(bank, num_batteries, &self.constraints, self.base))
+            .sum()
+    }
+
+    fn sum_joltage(&self, num_batteries: usize) -> Answer {
+        let mut joltage_sum = BigUint::ZERO;
+        for bank in &self.banks {
+            joltage_sum += value(
+                bank,
+                &self.select(bank, num_batteries, Goal::Largest)?,
+                self.base,
+            );
+        }
+
+        Ok(joltage_sum)
+    }
+}
+
+impl Day for Day3 {
+    fn part_1(&self) -> Answer {
+        self.sum_joltage(self.num_batteries[0])
+    }
+
+    fn part_2(&self) -> Answer {
+        self.sum_joltage(self.num_batteries[1])
+    }
+
+    fn oracle(&self) -> Option<&dyn Oracle> {
+        Some(self)
+    }
+
+    fn reporter(&self) -> Option<&dyn Report> {
+        Some(self)
+    }
+}
+
+impl Report for Day3 {
+    fn report(&self) -> Result<Table, AoCError> {
+        let mut table = Table::new(&[
+            "line",
+            "batteries",
+            "largest",
+            "largest positions",
+            "smallest",
+            "smallest positions",
+        ]);
+        // Joltage and positions of the selection, a bank without any is reported as infeasible
+        let show = |bank: &[u8], num_batteries: usize, goal: Goal| match self.select(
+            bank,
+            num_batteries,
+            goal,
+        ) {
+            Ok(chosen) => [
+                value(bank, &chosen, self.base).to_string(),
+                chosen
+                    .iter()
+                    .map(|idx| idx.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ],
+            Err(_) => ["infeasible".to_string(), String::new()],
+        };
+        for (l, bank) in (1..).zip(&self.banks) {
+            for num_batteries in self.num_batteries {
+                let mut row = vec![l.to_string(), num_batteries.to_string()];
+                row.extend(show(bank, num_batteries, Goal::Largest));
+                row.extend(show(bank, num_batteries, Goal::Smallest));
+                table.push(row);
+            }
+        }
+
+        Ok(table)
+    }
+}
+
+impl Oracle for Day3 {
+    fn reference_part_1(&self) -> Answer {
+        self.sum_joltage_reference(self.num_batteries[0])
+    }
+
+    fn reference_part_2(&self) -> Answer {
+        self.sum_joltage_reference(self.num_batteries[1])
+    }
+}
@@ -0,0 +1,550 @@
+use crate::check::Oracle;
+use crate::day::AoCError::{LogicError, ParamError, ParseError};
+use crate::day::{Answer, AoCError, Day, Int, Params};
+use crate::query::{Query, required_arg};
+use crate::report::{Report, Table};
+use crate::visualize::{Frame, Visualize};
+use nalgebra::DMatrix;
+use rand::Rng;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Neighbourhood {
+    /// Cells within the Manhattan distance
+    VonNeumann,
+    /// Cells within the Chebyshev distance
+    Moore,
+}
+
+impl FromStr for Neighbourhood {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "von-neumann" => Ok(Self::VonNeumann),
+            "moore" => Ok(Self::Moore),
+            _ => Err(format!(
+                "unknown neighbourhood '{s}', expected von-neumann or moore"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+}
+
+impl FromStr for Comparison {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lt" => Ok(Self::Less),
+            "le" => Ok(Self::LessEqual),
+            "gt" => Ok(Self::Greater),
+            "ge" => Ok(Self::GreaterEqual),
+            "eq" => Ok(Self::Equal),
+            _ => Err(format!(
+                "unknown comparison '{s}', expected lt, le, gt, ge or eq"
+            )),
+        }
+    }
+}
+
+/// A roll is removable if the number of rolls in its neighbourhood (excluding itself) compares
+/// to the threshold as configured
+#[derive(Debug, Clone)]
+struct Rule {
+    threshold: usize,
+    comparison: Comparison,
+    /// Neighbours beyond an edge wrap around to the opposite edge
+    wrap: bool,
+    offsets: Vec<(isize, isize)>,
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::new(Neighbourhood::Moore, 1, 4, Comparison::Less, false).unwrap()
+    }
+}
+
+impl Rule {
+    fn new(
+        neighbourhood: Neighbourhood,
+        radius: usize,
+        threshold: usize,
+        comparison: Comparison,
+        wrap: bool,
+    ) -> Result<Self, AoCError> {
+        if radius == 0 {
+            return Err(ParamError("the radius must be at least 1".to_string()));
+        }
+
+        let r = isize::try_from(radius)
+            .map_err(|_| ParamError(format!("the radius {radius} is too large")))?;
+        let offsets = (-r..=r)
+            .flat_map(|dr| (-r..=r).map(move |dc| (dr, dc)))
+            .filter(|&offset| offset != (0, 0))
+            .filter(|&(dr, dc)| match neighbourhood {
+                Neighbourhood::VonNeumann => dr.abs() + dc.abs() <= r,
+                Neighbourhood::Moore => true,
+            })
+            .collect();
+        Ok(Self {
+            threshold,
+            comparison,
+            wrap,
+            offsets,
+        })
+    }
+
+    /// Neighbours of `(r, c)`. On a wrapping grid smaller than the neighbourhood a cell can be
+    /// its own neighbour or appear several times
+    fn neighbours(
+        &self,
+        grid: &DMatrix<char>,
+        r: usize,
+        c: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (n_rows, n_cols) = (grid.nrows() as isize, grid.ncols() as isize);
+        self.offsets.iter().filter_map(move |&(dr, dc)| {
+            let (nr, nc) = (r as isize + dr, c as isize + dc);
+            if self.wrap {
+                Some((
+                    nr.rem_euclid(n_rows) as usize,
+                    nc.rem_euclid(n_cols) as usize,
+                ))
+            } else if (0..n_rows).contains(&nr) && (0..n_cols).contains(&nc) {
+                Some((nr as usize, nc as usize))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Removing rolls only lowers neighbour counts, so under a monotone rule a removable roll
+    /// stays removable and the final grid does not depend on the removal order
+    fn is_monotone(&self) -> bool {
+        matches!(self.comparison, Comparison::Less | Comparison::LessEqual)
+    }
+
+    fn accepts(&self, num_rolls: usize) -> bool {
+        match self.comparison {
+            Comparison::Less => num_rolls < self.threshold,
+            Comparison::LessEqual => num_rolls <= self.threshold,
+            Comparison::Greater => num_rolls > self.threshold,
+            Comparison::GreaterEqual => num_rolls >= self.threshold,
+            Comparison::Equal => num_rolls == self.threshold,
+        }
+    }
+}
+
+/// How the rolls removed in one wave are determined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Semantics {
+    /// All rolls removable in the grid at the start of the wave
+    Synchronous,
+    /// Scanning row by row and removing each removable roll immediately, so that later rolls of
+    /// the same wave see the removal
+    Sequential,
+}
+
+impl FromStr for Semantics {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "synchronous" => Ok(Self::Synchronous),
+            "sequential" => Ok(Self::Sequential),
+            _ => Err(format!(
+                "unknown semantics '{s}', expected synchronous or sequential"
+            )),
+        }
+    }
+}
+
+/// Outcome of removing rolls wave by wave until none is removable
+struct Waves {
+    /// Wave in which the roll at each position is removed, starting at 1
+    removed_in: DMatrix<Option<usize>>,
+    /// Number of rolls removed per wave
+    sizes: Vec<Int>,
+    /// Grid after the last wave with removed rolls marked as `x`
+    grid: DMatrix<char>,
+}
+
+/// Characters allowed in the input, a roll and an empty cell
+const CELLS: [char; 2] = ['@', '.'];
+
+pub struct Day4 {
+    grid: DMatrix<char>,
+    rule: Rule,
+    semantics: Semantics,
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut row = (0..size)
+                .map(|_| if rng.random_bool(0.6) { '@' } else { '.' })
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+impl Day4 {
+    pub fn new(data: &str) -> Result<Self, AoCError> {
+        let data = data.replace("\r\n", "\n").replace('\r', "\n");
+        let lines = data.trim_end_matches('\n').lines().collect::<Vec<_>>();
+        if lines.is_empty() {
+            return Err(ParseError("No data".to_string()));
+        }
+
+        let n_rows = lines.len();
+        let n_cols = lines[0].chars().count();
+        for (l, line) in (1..).zip(&lines) {
+            if line.chars().count() != n_cols {
+                return Err(ParseError(format!(
+                    "line {l} has {} cells, expected {n_cols} like line 1",
+                    line.chars().count()
+                )));
+            }
+
+            if let Some((c, cell)) = (1..)
+                .zip(line.chars())
+                .find(|(_, cell)| !CELLS.contains(cell))
+            {
+                return Err(ParseError(format!(
+                    "line {l}, column {c}: invalid cell {cell:?}, expected one of {CELLS:?}"
+                )));
+            }
+        }
+
+        let grid = DMatrix::from_row_iterator(n_rows, n_cols, lines.iter().flat_map(|l| l.chars()));
+        Ok(Self {
+            grid,
+            rule: Rule::default(),
+            semantics: Semantics::Sequential,
+        })
+    }
+
+    /// Supports the parameters `neighbourhood` (moore or von-neumann), `radius`, `threshold`,
+    /// `comparison` (lt, le, gt, ge or eq) and `wrap` (true for toroidal edges) of the rule, and
+    /// the `semantics` (sequential or synchronous) of a removal wave
+    pub fn with_params(data: &str, params: &Params) -> Result<Self, AoCError> {
+        let day = Self::new(data)?;
+        let wrap = params.get("wrap")?.unwrap_or(false);
+        // Any radius reaching beyond the grid covers the same cells unless the edges wrap, in
+        // which case the neighbourhood would hold each cell many times
+        let (n_rows, n_cols) = day.grid.shape();
+        let reach = n_rows + n_cols;
+        let mut radius = params.get("radius")?.unwrap_or(1);
+        if radius > reach {
+            if wrap {
+                return Err(LogicError(format!(
+                    "wrapping radius {radius} exceeds the {n_rows} rows plus {n_cols} columns"
+                )));
+            }
+
+            radius = reach.max(1);
+        }
+
+        let rule = Rule::new(
+            params.get("neighbourhood")?.unwrap_or(Neighbourhood::Moore),
+            radius,
+            params.get("threshold")?.unwrap_or(4),
+            params.get("comparison")?.unwrap_or(Comparison::Less),
+            wrap,
+        )?;
+        Ok(Self {
+            rule,
+            semantics: params.get("semantics")?.unwrap_or(Semantics::Sequential),
+            ..day
+        })
+    }
+}
+
+fn matrix_iter(matrix: &DMatrix<char>, mut f: impl FnMut(usize, usize, &char)) {
+    for r in 0..matrix.nrows() {
+        for c in 0..matrix.ncols() {
+            f(r, c, &matrix[(r, c)]);
+        }
+    }
+}
+
+fn removable(grid: &DMatrix<char>, r: usize, c: usize, rule: &Rule) -> bool {
+    let num_rolls = rule
+        .neighbours(grid, r, c)
+        .filter(|&pos| grid[pos] == '@')
+        .count();
+    rule.accepts(num_rolls)
+}
+
+/// Removes one wave of rolls and returns their positions
+fn removal_pass(
+    grid: &mut DMatrix<char>,
+    rule: &Rule,
+    semantics: Semantics,
+) -> Vec<(usize, usize)> {
+    let mut removed = Vec::new();
+    for r in 0..grid.nrows() {
+        for c in 0..grid.ncols() {
+            if grid[(r, c)] == '@' && removable(grid, r, c, rule) {
+                if semantics == Semantics::Sequential {
+                    grid[(r, c)] = 'x';
+                }
+                removed.push((r, c));
+            }
+        }
+    }
+
+    for &pos in &removed {
+        grid[pos] = 'x';
+    }
+
+    removed
+}
+
+fn waves(grid: &DMatrix<char>, rule: &Rule, semantics: Semantics) -> Waves {
+    let mut waves = Waves {
+        removed_in: DMatrix::from_element(grid.nrows(), grid.ncols(), None),
+        sizes: Vec::new(),
+        grid: grid.clone(),
+    };
+    loop {
+        let removed = removal_pass(&mut waves.grid, rule, semantics);
+        if removed.is_empty() {
+            return waves;
+        }
+
+        waves.sizes.push(removed.len() as Int);
+        for pos in removed {
+            waves.removed_in[pos] = Some(waves.sizes.len());
+        }
+    }
+}
+
+/// Removes rolls until none is removable, re-examining only the neighbours of removed rolls.
+/// Requires a monotone rule, under which both semantics remove the same rolls
+fn remove_all(grid: &mut DMatrix<char>, rule: &Rule) -> Int {
+    let mut counts = DMatrix::<usize>::zeros(grid.nrows(), grid.ncols());
+    let mut worklist = Vec::new();
+    matrix_iter(grid, |r, c, &val| {
+        if val != '@' {
+            return;
+        }
+
+        counts[(r, c)] = rule
+            .neighbours(grid, r, c)
+            .filter(|&pos| grid[pos] == '@')
+            .count();
+        if rule.accepts(counts[(r, c)]) {
+            worklist.push((r, c));
+        }
+    });
+
+    let mut num_removed: Int = 0;
+    while let Some(pos) = worklist.pop() {
+        if grid[pos] != '@' {
+            continue;
+        }
+
+        grid[pos] = 'x';
+        num_removed += 1;
+        for neighbour in rule.neighbours(grid, pos.0, pos.1) {
+            if grid[neighbour] == '@' {
+                counts[neighbour] -= 1;
+                if rule.accepts(counts[neighbour]) {
+                    worklist.push(neighbour);
+                }
+            }
+        }
+    }
+
+    num_removed
+}
+
+impl Day for Day4 {
+    fn part_1(&self) -> Answer {
+        let mut num_movable = 0 as Int;
+        matrix_iter(&self.grid, |r, c, &val| {
+            if val != '@' {
+                return;
+            }
+
+            num_movable += removable(&self.grid, r, c, &self.rule) as Int;
+        });
+
+        Ok(num_movable.into())
+    }
+
+    fn part_2(&self) -> Answer {
+        let mut grid = self.grid.clone();
+        if !self.rule.is_monotone() {
+            let waves = waves(&grid, &self.rule, self.semantics);
+            return Ok(waves.sizes.iter().sum::<Int>().into());
+        }
+
+        Ok(remove_all(&mut grid, &self.rule).into())
+    }
+
+    fn oracle(&self) -> Option<&dyn Oracle> {
+        Some(self)
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+
+    fn reporter(&self) -> Option<&dyn Report> {
+        Some(self)
+    }
+
+    fn querier(&self) -> Option<&dyn Query> {
+        Some(self)
+    }
+}
+
+impl Report for Day4 {
+    fn report(&self) -> Result<Table, AoCError> {
+        let waves = waves(&self.grid, &self.rule, self.semantics);
+        let mut table = Table::new(&["row", "column", "wave"]);
+        matrix_iter(&self.grid, |r, c, &val| {
+            if val == '@' {
+                table.push(vec![
+                    r.to_string(),
+                    c.to_string(),
+                    show_wave(waves.removed_in[(r, c)]),
+                ]);
+            }
+        });
+
+        Ok(table)
+    }
+}
+
+fn show_wave(wave: Option<usize>) -> String {
+    wave.map_or("never".to_string(), |w| w.to_string())
+}
+
+impl Query for Day4 {
+    fn queries(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("waves", "number of rolls removed in each wave"),
+            (
+                "wave ROW COLUMN",
+                "wave in which the roll at ROW COLUMN is removed",
+            ),
+            ("final", "grid after the last wave"),
+        ]
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> Result<String, AoCError> {
+        let waves = waves(&self.grid, &self.rule, self.semantics);
+        match name {
+            "waves" => Ok((1..)
+                .zip(&waves.sizes)
+                .map(|(wave, size)| format!("{wave}: {size}"))
+                .collect::<Vec<_>>()
+                .join(", ")),
+            "wave" => {
+                let pos = (required_arg(args, 0)?, required_arg(args, 1)?);
+                if pos.0 >= self.grid.nrows() || pos.1 >= self.grid.ncols() {
+                    return Err(ParamError(format!("{pos:?} is outside of the grid")));
+                }
+
+                Ok(match self.grid[pos] {
+                    '@' => show_wave(waves.removed_in[pos]),
+                    _ => "no roll".to_string(),
+                })
+            }
+            "final" => Ok(waves
+                .grid
+                .row_iter()
+                .map(|row| "\n".to_string() + &row.iter().collect::<String>())
+                .collect()),
+            _ => Err(ParamError(format!("unknown query {name}"))),
+        }
+    }
+}
+
+impl Oracle for Day4 {
+    fn reference_part_1(&self) -> Answer {
+        self.part_1()
+    }
+
+    fn reference_part_2(&self) -> Answer {
+        let waves = waves(&self.grid, &self.rule, self.semantics);
+        Ok(waves.sizes.iter().sum::<Int>().into())
+    }
+}
+
+impl Visualize for Day4 {
+    fn frames(&self) -> Result<Vec<Frame>, AoCError> {
+        let waves = waves(&self.grid, &self.rule, self.semantics);
+        let frames = (0..=waves.sizes.len())
+            .map(|wave| {
+                self.grid
+                    .zip_map(&waves.removed_in, |cell, removed_in| match removed_in {
+                        Some(w) if w <= wave => 'x',
+                        _ => cell,
+                    })
+            })
+            .collect();
+        Ok(frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(input: &str) -> String {
+        match Day4::new(input) {
+            Err(ParseError(msg)) => msg,
+            Err(e) => panic!("unexpected error {e}"),
+            Ok(_) => panic!("{input:?} parsed"),
+        }
+    }
+
+    #[test]
+    fn line_endings() {
+        let grid = Day4::new("@.@\n.@@\n").unwrap().grid;
+        assert_eq!(grid.shape(), (2, 3));
+        for input in ["@.@\r\n.@@\r\n", "@.@\r.@@\r", "@.@\r\n.@@"] {
+            assert_eq!(Day4::new(input).unwrap().grid, grid, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn ragged_lines() {
+        assert_eq!(
+            parse_error("@@@\n@@\n@@@\n"),
+            "line 2 has 2 cells, expected 3 like line 1"
+        );
+        assert_eq!(
+            parse_error("@@\r\n@@\r\n@.@\r\n"),
+            "line 3 has 3 cells, expected 2 like line 1"
+        );
+    }
+
+    #[test]
+    fn invalid_cells() {
+        assert_eq!(
+            parse_error("@.\n@x\n"),
+            "line 2, column 2: invalid cell 'x', expected one of ['@', '.']"
+        );
+        assert_eq!(
+            parse_error("@\t.\n"),
+            "line 1, column 2: invalid cell '\\t', expected one of ['@', '.']"
+        );
+        assert_eq!(parse_error("\r\n\n"), "No data");
+    }
+}
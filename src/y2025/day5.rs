@@ -0,0 +1,529 @@
+use crate::check::Oracle;
+use crate::day::AoCError::ParamError;
+use crate::day::{Answer, AoCError, Day};
+use crate::query::{Query, required_arg};
+use crate::report::{Report, Table};
+use rand::Rng;
+use std::cmp::max;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone)]
+struct Range {
+    start: usize,
+    end: usize,
+}
+
+impl Range {
+    /// Parses `a-b` and `a..=b` (inclusive), `a..b` (half-open) or `a-` (open-ended)
+    fn new(data: &str) -> Result<Self, String> {
+        let bound = |s: &str, name: &str| {
+            s.parse::<usize>()
+                .map_err(|e| format!("invalid {name} in {data}: {e}"))
+        };
+        let (start, end, half_open) = if let Some((start, end)) = data.split_once("..=") {
+            (start, end, false)
+        } else if let Some((start, end)) = data.split_once("..") {
+            (start, end, true)
+        } else if let Some((start, end)) = data.split_once('-') {
+            (start, end, false)
+        } else {
+            return Err(format!("invalid range: {data}"));
+        };
+        let start = bound(start, "start")?;
+        let end = match end {
+            "" if data.ends_with('-') => usize::MAX,
+            end => bound(end, "end")?,
+        };
+        if end < start {
+            return Err(format!(
+                "reversed range: {data} ends at {end} before its start {start}"
+            ));
+        }
+        if half_open && end == start {
+            return Err(format!("empty range: {data}"));
+        }
+
+        let end = if half_open { end - 1 } else { end };
+        Ok(Self { start, end })
+    }
+
+    fn contains(&self, x: usize) -> bool {
+        x >= self.start && x <= self.end
+    }
+
+    fn len(&self) -> u128 {
+        (self.end - self.start) as u128 + 1
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.end {
+            usize::MAX => write!(f, "{}-", self.start),
+            end => write!(f, "{}-{end}", self.start),
+        }
+    }
+}
+
+/// Disjoint fresh ranges sorted by start, built once to answer membership by binary search
+struct RangeIndex {
+    merged: Vec<Range>,
+}
+
+impl RangeIndex {
+    fn new(ranges: &[Range]) -> Self {
+        let mut sorted = ranges.to_vec();
+        sorted.sort_unstable_by_key(|r| r.start);
+        let mut merged: Vec<Range> = Vec::with_capacity(sorted.len());
+        for r in sorted {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end.saturating_add(1) => {
+                    last.end = max(last.end, r.end)
+                }
+                _ => merged.push(r),
+            }
+        }
+
+        Self { merged }
+    }
+
+    /// Index of the merged range containing `id`, or the index of the first range after it
+    fn find(&self, id: usize) -> Result<usize, usize> {
+        let idx = self.merged.partition_point(|r| r.start <= id);
+        match idx.checked_sub(1) {
+            Some(prev) if self.merged[prev].contains(id) => Ok(prev),
+            _ => Err(idx),
+        }
+    }
+
+    fn contains(&self, id: usize) -> bool {
+        self.find(id).is_ok()
+    }
+
+    /// Closest range to `id` with its distance, preferring the lower one on ties
+    fn nearest(&self, id: usize) -> Option<(&Range, usize)> {
+        match self.find(id) {
+            Ok(idx) => Some((&self.merged[idx], 0)),
+            Err(idx) => {
+                let below = idx
+                    .checked_sub(1)
+                    .map(|i| (&self.merged[i], id - self.merged[i].end));
+                let above = self.merged.get(idx).map(|r| (r, r.start - id));
+                match (below, above) {
+                    (Some(b), Some(a)) => Some(if a.1 < b.1 { a } else { b }),
+                    (b, a) => b.or(a),
+                }
+            }
+        }
+    }
+
+    fn num_ids(&self) -> u128 {
+        self.merged.iter().map(Range::len).sum()
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let universe = size * 1_000_000;
+    let max_width = universe / size * 2;
+    let num_databases = rng.random_range(1..=3);
+    let mut data = String::new();
+    for database in 0..num_databases {
+        if database > 0 {
+            data.push('\n');
+        }
+
+        for _ in 0..size.div_ceil(num_databases) {
+            let start = rng.random_range(1..universe);
+            let end = start + rng.random_range(0..max_width);
+            match rng.random_range(0..100) {
+                0..10 => data.push_str(&format!("{start}..{}\n", end + 1)),
+                10..20 => data.push_str(&format!("{start}..={end}\n")),
+                20 => data.push_str(&format!("{start}-\n")),
+                _ => data.push_str(&format!("{start}-{end}\n")),
+            }
+        }
+    }
+
+    data.push('\n');
+    for _ in 0..size {
+        data.push_str(&format!("{}\n", rng.random_range(1..universe + max_width)));
+    }
+
+    data
+}
+
+pub struct Day5 {
+    /// Ranges of all databases
+    fresh: Vec<Range>,
+    ids: Vec<usize>,
+    /// Union of all databases
+    index: RangeIndex,
+    databases: Vec<RangeIndex>,
+}
+
+impl Day5 {
+    /// Sections separated by blank lines: one or more databases of fresh ranges optionally
+    /// followed by the ids to check, a last section made only of integers holds the ids
+    pub fn new(data: &str) -> Result<Self, AoCError> {
+        let mut sections: Vec<Vec<(usize, &str)>> = vec![Vec::new()];
+        for (l, line) in (1..).zip(data.lines()) {
+            match line.is_empty() {
+                true if !sections.last().unwrap().is_empty() => sections.push(Vec::new()),
+                true => {}
+                false => sections.last_mut().unwrap().push((l, line)),
+            }
+        }
+
+        if sections.last().unwrap().is_empty() {
+            sections.pop();
+        }
+
+        let is_id = |line: &str| line.bytes().all(|b| b.is_ascii_digit());
+        let id_section = match sections.last() {
+            Some(last) if sections.len() > 1 && last.iter().all(|&(_, line)| is_id(line)) => {
+                sections.pop().unwrap()
+            }
+            _ => Vec::new(),
+        };
+        let parse_error =
+            |l: usize, msg: String| AoCError::ParseError(format!("error in line {l}: {msg}"));
+        let mut fresh = Vec::new();
+        let mut databases = Vec::with_capacity(sections.len());
+        for section in sections {
+            let ranges = section
+                .into_iter()
+                .map(|(l, line)| match is_id(line) {
+                    true => Err(parse_error(
+                        l,
+                        format!("id {line} among ranges, ids go in a last section of their own"),
+                    )),
+                    false => Range::new(line).map_err(|msg| parse_error(l, msg)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            databases.push(RangeIndex::new(&ranges));
+            fresh.extend(ranges);
+        }
+
+        let ids = id_section
+            .into_iter()
+            .map(|(l, line)| {
+                line.parse::<usize>()
+                    .map_err(|e| parse_error(l, e.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            index: RangeIndex::new(&fresh),
+            fresh,
+            ids,
+            databases,
+        })
+    }
+
+    fn membership(&self, id: usize) -> Vec<bool> {
+        self.databases.iter().map(|db| db.contains(id)).collect()
+    }
+
+    /// Number of ids whose membership in the databases satisfies `category`. The databases are
+    /// constant between consecutive range boundaries, so checking one id per segment suffices
+    fn measure(&self, category: impl Fn(&[bool]) -> bool) -> u128 {
+        let mut bounds = self
+            .databases
+            .iter()
+            .flat_map(|db| &db.merged)
+            .flat_map(|r| [r.start as u128, r.end as u128 + 1])
+            .collect::<Vec<_>>();
+        bounds.sort_unstable();
+        bounds.dedup();
+        bounds
+            .windows(2)
+            .filter(|w| category(&self.membership(w[0] as usize)))
+            .map(|w| w[1] - w[0])
+            .sum()
+    }
+
+    fn database_arg(&self, args: &[&str], idx: usize) -> Result<usize, AoCError> {
+        let db: usize = required_arg(args, idx)?;
+        if !(1..=self.databases.len()).contains(&db) {
+            return Err(ParamError(format!(
+                "there is no database {db}, the input has {}",
+                self.databases.len()
+            )));
+        }
+
+        Ok(db - 1)
+    }
+}
+
+/// Category of an id by the databases containing it
+fn category(membership: &[bool]) -> String {
+    let containing = (1..)
+        .zip(membership)
+        .filter(|&(_, &member)| member)
+        .map(|(db, _)| db)
+        .collect::<Vec<usize>>();
+    match containing.as_slice() {
+        [] => "none".to_string(),
+        _ if containing.len() == membership.len() => "all".to_string(),
+        [db] => format!("only {db}"),
+        _ => "several".to_string(),
+    }
+}
+
+impl Day for Day5 {
+    fn part_1(&self) -> Answer {
+        let num_fresh = self
+            .ids
+            .iter()
+            .filter(|&&id| self.index.contains(id))
+            .count();
+        Ok(num_fresh.into())
+    }
+
+    fn part_2(&self) -> Answer {
+        Ok(self.index.num_ids().into())
+    }
+
+    fn oracle(&self) -> Option<&dyn Oracle> {
+        Some(self)
+    }
+
+    fn querier(&self) -> Option<&dyn Query> {
+        Some(self)
+    }
+
+    fn reporter(&self) -> Option<&dyn Report> {
+        Some(self)
+    }
+}
+
+impl Report for Day5 {
+    fn report(&self) -> Result<Table, AoCError> {
+        let mut table = Table::new(&["id", "databases", "category"]);
+        for &id in &self.ids {
+            let membership = self.membership(id);
+            let containing = (1..)
+                .zip(&membership)
+                .filter(|&(_, &member)| member)
+                .map(|(db, _): (usize, _)| db.to_string())
+                .collect::<Vec<_>>();
+            table.push(vec![
+                id.to_string(),
+                containing.join(" "),
+                category(&membership),
+            ]);
+        }
+
+        Ok(table)
+    }
+}
+
+impl Query for Day5 {
+    fn queries(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("fresh ID", "whether the id is fresh"),
+            ("covering ID", "input ranges containing the id"),
+            (
+                "nearest ID",
+                "closest fresh range to the id and its distance",
+            ),
+            (
+                "sets",
+                "union, intersection, symmetric difference and exclusive sizes of the databases",
+            ),
+            (
+                "difference A B",
+                "number of ids fresh in database A but not in database B",
+            ),
+        ]
+    }
+
+    fn query(&self, name: &str, args: &[&str]) -> Result<String, AoCError> {
+        let id = || required_arg::<usize>(args, 0);
+        match name {
+            "fresh" => Ok(self.index.contains(id()?).to_string()),
+            "covering" => {
+                let id = id()?;
+                let covering = (1..)
+                    .zip(&self.fresh)
+                    .filter(|(_, r)| r.contains(id))
+                    .map(|(idx, r)| format!("{r} (range {idx})"))
+                    .collect::<Vec<_>>();
+                Ok(match covering.is_empty() {
+                    true => "none".to_string(),
+                    false => covering.join(", "),
+                })
+            }
+            "nearest" => Ok(match self.index.nearest(id()?) {
+                Some((r, 0)) => format!("fresh in {r}"),
+                Some((r, distance)) => format!("{r} at distance {distance}"),
+                None => "there are no fresh ranges".to_string(),
+            }),
+            "sets" => {
+                let num_members = |m: &[bool]| m.iter().filter(|&&member| member).count();
+                let mut sizes = vec![
+                    format!("union: {}", self.measure(|m| num_members(m) > 0)),
+                    format!(
+                        "intersection: {}",
+                        self.measure(|m| num_members(m) == m.len())
+                    ),
+                    format!(
+                        "symmetric difference: {}",
+                        self.measure(|m| num_members(m) % 2 == 1)
+                    ),
+                ];
+                sizes.extend((0..self.databases.len()).map(|db| {
+                    let only = self.measure(|m| m[db] && num_members(m) == 1);
+                    format!("only {}: {only}", db + 1)
+                }));
+                Ok(sizes.join(", "))
+            }
+            "difference" => {
+                let a = self.database_arg(args, 0)?;
+                let b = self.database_arg(args, 1)?;
+                Ok(self.measure(|m| m[a] && !m[b]).to_string())
+            }
+            _ => Err(ParamError(format!("unknown query {name}"))),
+        }
+    }
+}
+
+impl Oracle for Day5 {
+    fn reference_part_1(&self) -> Answer {
+        Ok(self
+            .ids
+            .iter()
+            .filter(|&&id| self.fresh.iter().any(|r| r.contains(id)))
+            .count()
+            .into())
+    }
+
+    /// Splits the number line at every range boundary and counts the elementary intervals that
+    /// are covered by any range
+    fn reference_part_2(&self) -> Answer {
+        let mut bounds = self
+            .fresh
+            .iter()
+            .flat_map(|r| [r.start as u128, r.end as u128 + 1])
+            .collect::<Vec<_>>();
+        bounds.sort_unstable();
+        bounds.dedup();
+        let mut res: u128 = 0;
+        for window in bounds.windows(2) {
+            if self.fresh.iter().any(|r| r.contains(window[0] as usize)) {
+                res += window[1] - window[0];
+            }
+        }
+
+        Ok(res.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Three databases with adjacent, half-open, inclusive and open-ended ranges
+    const DATABASES: &str = "1-3\n4-6\n10-12\n\n5..9\n12-\n\n2..=5\n11-11\n\n1\n";
+    /// Inclusive bounds of the ranges of each database
+    const BOUNDS: [&[(usize, usize)]; 3] = [
+        &[(1, 3), (4, 6), (10, 12)],
+        &[(5, 8), (12, usize::MAX)],
+        &[(2, 5), (11, 11)],
+    ];
+    /// Every id beyond is only in the open-ended range of the second database
+    const UNIVERSE: usize = 20;
+
+    /// Number of ids whose membership satisfies `category`, checking the ids up to the universe
+    /// one by one and those beyond it at once
+    fn brute_force(category: impl Fn(&[bool]) -> bool) -> u128 {
+        let membership = |id: usize| {
+            BOUNDS
+                .iter()
+                .map(|db| db.iter().any(|&(start, end)| (start..=end).contains(&id)))
+                .collect::<Vec<_>>()
+        };
+        let small = (0..=UNIVERSE)
+            .filter(|&id| category(&membership(id)))
+            .count() as u128;
+        match category(&membership(UNIVERSE + 1)) {
+            true => small + (usize::MAX - UNIVERSE) as u128,
+            false => small,
+        }
+    }
+
+    #[test]
+    fn range_syntax() {
+        let cases = [
+            ("3-5", 3, 5),
+            ("3..6", 3, 5),
+            ("3..=5", 3, 5),
+            ("3-", 3, usize::MAX),
+            ("0..1", 0, 0),
+            ("7..=7", 7, 7),
+        ];
+        for (input, start, end) in cases {
+            let range = Range::new(input).unwrap();
+            assert_eq!((range.start, range.end), (start, end), "{input}");
+        }
+
+        let day = Day5::new("3-\n1..3\n\n2\n").unwrap();
+        assert_eq!(day.part_2().unwrap().to_string(), usize::MAX.to_string());
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        let cases = [
+            ("5-3", "reversed range: 5-3 ends at 3 before its start 5"),
+            ("5..3", "reversed range: 5..3 ends at 3 before its start 5"),
+            (
+                "5..=3",
+                "reversed range: 5..=3 ends at 3 before its start 5",
+            ),
+            ("5..5", "empty range: 5..5"),
+            (
+                "3..",
+                "invalid end in 3..: cannot parse integer from empty string",
+            ),
+            ("3", "invalid range: 3"),
+        ];
+        for (input, msg) in cases {
+            assert_eq!(Range::new(input).unwrap_err(), msg);
+        }
+
+        match Day5::new("1-2\n5-3\n\n4\n") {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "Parse error: error in line 2: reversed range: 5-3 ends at 3 before its start 5"
+            ),
+            Ok(_) => panic!("reversed range parsed"),
+        }
+    }
+
+    #[test]
+    fn set_sizes() {
+        let day = Day5::new(DATABASES).unwrap();
+        let num_members = |m: &[bool]| m.iter().filter(|&&member| member).count();
+        let mut expected = format!(
+            "union: {}, intersection: {}, symmetric difference: {}",
+            brute_force(|m| num_members(m) > 0),
+            brute_force(|m| num_members(m) == 3),
+            brute_force(|m| num_members(m) % 2 == 1),
+        );
+        for db in 0..3 {
+            let only = brute_force(|m| m[db] && num_members(m) == 1);
+            expected.push_str(&format!(", only {}: {only}", db + 1));
+        }
+        assert_eq!(day.query("sets", &[]).unwrap(), expected);
+
+        for a in 1..=3 {
+            for b in 1..=3 {
+                let args = [a.to_string(), b.to_string()];
+                assert_eq!(
+                    day.query("difference", &[&args[0], &args[1]]).unwrap(),
+                    brute_force(|m| m[a - 1] && !m[b - 1]).to_string(),
+                    "difference {a} {b}"
+                );
+            }
+        }
+    }
+}
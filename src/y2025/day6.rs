@@ -0,0 +1,190 @@
+use crate::day::AoCError::{LogicError, ParseError};
+use crate::day::{Answer, AoCError, Day, Int, Res};
+use nalgebra::DMatrix;
+use rand::Rng;
+use std::iter::repeat;
+
+#[derive(Debug)]
+enum Op {
+    Add,
+    Mul,
+}
+
+impl Op {
+    fn new(op: &str) -> Result<Self, AoCError> {
+        match op {
+            "+" => Ok(Self::Add),
+            "*" => Ok(Self::Mul),
+            _ => Err(ParseError(format!("invalid op {op}"))),
+        }
+    }
+
+    /// None on overflow
+    fn apply(&self, a: Int, b: Int) -> Option<Int> {
+        match self {
+            Self::Add => a.checked_add(b),
+            Self::Mul => a.checked_mul(b),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Problem {
+    numbers: Vec<Int>,
+    op: Op,
+}
+
+pub struct Day6 {
+    problems: Vec<Problem>,
+    problems_part_2: Vec<Problem>,
+}
+
+fn build_problems<'a>(
+    numbers: Vec<Vec<Int>>,
+    ops: impl Iterator<Item = &'a str>,
+) -> Result<Vec<Problem>, AoCError> {
+    let ops = ops.map(Op::new).collect::<Result<Vec<_>, _>>()?;
+    if ops.len() != numbers.len() {
+        return Err(ParseError(format!(
+            "found {} problems but {} operators",
+            numbers.len(),
+            ops.len()
+        )));
+    }
+
+    Ok(numbers
+        .into_iter()
+        .zip(ops)
+        .map(|(numbers, op)| Problem { numbers, op })
+        .collect())
+}
+
+fn parse_part_2(number_lines: &[&str], op_line: &str) -> Result<Vec<Problem>, AoCError> {
+    // lines may be trimmed, so pad them to a common width
+    let n_cols = number_lines
+        .iter()
+        .map(|l| l.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mat = DMatrix::from_row_iterator(
+        number_lines.len(),
+        n_cols,
+        number_lines
+            .iter()
+            .flat_map(|l| l.chars().chain(repeat(' ')).take(n_cols)),
+    );
+
+    let mut numbers: Vec<Vec<Int>> = Vec::new();
+    numbers.push(Vec::new());
+    for (c, col) in mat.column_iter().enumerate().rev() {
+        if col.iter().all(|c| c.is_whitespace()) {
+            numbers.push(Vec::new());
+            continue;
+        }
+
+        let mut number = 0 as Int;
+        for &d in col.iter().filter(|c| !c.is_whitespace()) {
+            let digit = d
+                .to_digit(10)
+                .ok_or(ParseError(format!("column {}: invalid digit {d}", c + 1)))?;
+            number = number
+                .checked_mul(10)
+                .and_then(|n| n.checked_add(digit as Int))
+                .ok_or(ParseError(format!("column {}: number too large", c + 1)))?;
+        }
+
+        numbers.last_mut().unwrap().push(number);
+    }
+
+    build_problems(numbers, op_line.split_whitespace().rev())
+}
+
+const NUM_OPERANDS: usize = 4;
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut lines = vec![String::new(); NUM_OPERANDS + 1];
+    for p in 0..size {
+        if p > 0 {
+            lines.iter_mut().for_each(|l| l.push(' '));
+        }
+
+        let width = rng.random_range(1..=4);
+        let left_aligned = rng.random_bool(0.5);
+        for (n, line) in lines.iter_mut().take(NUM_OPERANDS).enumerate() {
+            // the widest number determines the column width
+            let digits = if n == 0 {
+                width
+            } else {
+                rng.random_range(1..=width)
+            };
+            let number = (0..digits)
+                .map(|_| char::from(b'0' + rng.random_range(1..=9)))
+                .collect::<String>();
+            if left_aligned {
+                line.push_str(&format!("{number:<width$}"));
+            } else {
+                line.push_str(&format!("{number:>width$}"));
+            }
+        }
+
+        let op = if rng.random_bool(0.5) { '+' } else { '*' };
+        lines[NUM_OPERANDS].push_str(&format!("{op:<width$}"));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+impl Day6 {
+    pub fn new(input: &str) -> Result<Self, AoCError> {
+        let mut numbers: Vec<Vec<Int>> = Vec::new();
+        let lines = input.lines().collect::<Vec<_>>();
+        let (&op_line, number_lines) = lines
+            .split_last()
+            .ok_or(ParseError("no data".to_string()))?;
+        for (l, &line) in (1..).zip(number_lines) {
+            for (p, n) in line.split_whitespace().enumerate() {
+                if p >= numbers.len() {
+                    numbers.push(Vec::new());
+                }
+
+                numbers[p].push(
+                    n.parse::<Int>()
+                        .map_err(|e| ParseError(format!("line {l}, pos {}: {e}", p + 1)))?,
+                );
+            }
+        }
+
+        Ok(Self {
+            problems: build_problems(numbers, op_line.split_whitespace())?,
+            problems_part_2: parse_part_2(number_lines, op_line)?,
+        })
+    }
+}
+
+fn grand_total(problems: &[Problem]) -> Res {
+    let overflow = || LogicError("grand total overflows".to_string());
+    let mut total: Int = 0;
+    for (idx, p) in (1..).zip(problems) {
+        let (&first, rest) = p
+            .numbers
+            .split_first()
+            .ok_or(LogicError(format!("problem {idx} has no numbers")))?;
+        let result = rest
+            .iter()
+            .try_fold(first, |acc, &val| p.op.apply(acc, val))
+            .ok_or_else(overflow)?;
+        total = total.checked_add(result).ok_or_else(overflow)?;
+    }
+
+    Ok(total)
+}
+
+impl Day for Day6 {
+    fn part_1(&self) -> Answer {
+        grand_total(&self.problems).map(Into::into)
+    }
+
+    fn part_2(&self) -> Answer {
+        grand_total(&self.problems_part_2).map(Into::into)
+    }
+}
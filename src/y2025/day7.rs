@@ -0,0 +1,185 @@
+use crate::day::AoCError::{LogicError, ParseError};
+use crate::day::{Answer, AoCError, Day, Int, Res};
+use crate::visualize::{Frame, Visualize};
+use nalgebra::DMatrix;
+use rand::Rng;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone)]
+struct Manifold {
+    field: DMatrix<char>,
+    num_visits: DMatrix<Int>,
+}
+
+impl Display for Manifold {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\n{}", self.field, self.num_visits)
+    }
+}
+
+impl Manifold {
+    fn new(input: &str) -> Result<Self, AoCError> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let num_cols = lines
+            .first()
+            .ok_or(ParseError("no lines".to_string()))?
+            .chars()
+            .count();
+        for (l, line) in (1..).zip(&lines) {
+            if line.chars().count() != num_cols {
+                return Err(ParseError(format!(
+                    "line {l} has {} cells, expected {num_cols}",
+                    line.chars().count()
+                )));
+            }
+
+            // splitters at the border would send beams off the manifold
+            if line.starts_with('^') || line.ends_with('^') {
+                return Err(ParseError(format!("line {l}: splitter at the border")));
+            }
+        }
+
+        let field = DMatrix::from_row_iterator(
+            lines.len(),
+            num_cols,
+            lines.iter().flat_map(|&l| l.chars()),
+        );
+        let mut num_visits = DMatrix::zeros(field.nrows(), field.ncols());
+        let start_pos = field
+            .row(0)
+            .iter()
+            .position(|&c| c == 'S')
+            .ok_or(ParseError("No start".to_string()))?;
+        num_visits[(0, start_pos)] = 1;
+        Ok(Self { field, num_visits })
+    }
+
+    fn step(&mut self, height: usize) -> Res {
+        if height >= self.field.nrows() - 1 {
+            return Ok(0);
+        }
+
+        let beams = self
+            .field
+            .row(height)
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, &c)| {
+                if c == '|' || c == 'S' {
+                    Some(idx)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let r = height;
+        let mut num_splits = 0;
+        for col in beams {
+            let current_n_visits = self.num_visits[(r, col)];
+            if self.field[(r + 1, col)] != '^' {
+                self.add_beam((r + 1, col), current_n_visits)?;
+            } else {
+                self.add_beam((r + 1, col - 1), current_n_visits)?;
+                self.add_beam((r + 1, col + 1), current_n_visits)?;
+                num_splits += 1;
+            }
+        }
+
+        Ok(num_splits)
+    }
+
+    fn add_beam(&mut self, pos: (usize, usize), n_visits: Int) -> Result<(), AoCError> {
+        self.field[pos] = '|';
+        self.num_visits[pos] = self.num_visits[pos]
+            .checked_add(n_visits)
+            .ok_or(LogicError("number of timelines overflows".to_string()))?;
+        Ok(())
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let width = (size.max(3) / 2) * 2 + 1;
+    let mid = width / 2;
+    let mut data = String::new();
+    for r in 0..size.max(2) {
+        let row = (0..width)
+            .map(|c| {
+                let level = r / 2;
+                let offset = c.abs_diff(mid);
+                let reachable = r % 2 == 0 && level >= 1 && offset < level;
+                if r == 0 && c == mid {
+                    'S'
+                } else if reachable
+                    && (offset + level) % 2 == 1
+                    && c > 0
+                    && c < width - 1
+                    && rng.random_bool(0.7)
+                {
+                    '^'
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        data.push_str(&row);
+        data.push('\n');
+    }
+
+    data
+}
+
+pub struct Day7 {
+    manifold: Manifold,
+}
+
+impl Day7 {
+    pub fn new(input: &str) -> Result<Self, AoCError> {
+        Ok(Self {
+            manifold: Manifold::new(input)?,
+        })
+    }
+}
+
+impl Day for Day7 {
+    fn part_1(&self) -> Answer {
+        let mut manifold = self.manifold.clone();
+        let mut num_splits_total = 0;
+        for height in 0..manifold.field.nrows() - 1 {
+            num_splits_total += manifold.step(height)?;
+        }
+
+        Ok(num_splits_total.into())
+    }
+
+    fn part_2(&self) -> Answer {
+        let mut manifold = self.manifold.clone();
+        for height in 0..manifold.field.nrows() - 1 {
+            manifold.step(height)?;
+        }
+
+        Ok(manifold
+            .num_visits
+            .row(manifold.field.nrows() - 1)
+            .iter()
+            .sum::<Int>()
+            .into())
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+}
+
+impl Visualize for Day7 {
+    fn frames(&self) -> Result<Vec<Frame>, AoCError> {
+        let mut manifold = self.manifold.clone();
+        let mut frames = vec![manifold.field.clone()];
+        for height in 0..manifold.field.nrows() - 1 {
+            manifold.step(height)?;
+            frames.push(manifold.field.clone());
+        }
+
+        Ok(frames)
+    }
+}
@@ -1,6 +1,7 @@
-use crate::day::{AoCError, Day, Int, Res};
+use crate::day::{Answer, AoCError, Day, Int};
 use distances::vectors::euclidean;
 use nalgebra::Point3;
+use rand::Rng;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt::Display;
@@ -77,6 +78,15 @@ impl DisjointSet {
     }
 }
 
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let [x, y, z] = [(); 3].map(|_| rng.random_range(0..100_000));
+            format!("{x},{y},{z}\n")
+        })
+        .collect()
+}
+
 pub struct Day8 {
     junction_boxes: Vec<JunctionBox>,
 }
@@ -117,7 +127,7 @@ where
 }
 
 impl Day for Day8 {
-    fn part_1(&self) -> Res {
+    fn part_1(&self) -> Answer {
         let cartesian = sorted_cartesian(&self.junction_boxes, |p1, p2| {
             p1.0.distance(p1.1).total_cmp(&p2.0.distance(p2.1))
         });
@@ -133,15 +143,17 @@ impl Day for Day8 {
 
         let mut largest: Vec<_> = sizes.into_values().collect();
         largest.sort_unstable();
-        Ok(largest.iter().rev().take(3).product())
+        Ok(largest.iter().rev().take(3).product::<Int>().into())
     }
 
-    fn part_2(&self) -> Res {
+    fn part_2(&self) -> Answer {
         let cartesian = sorted_cartesian(&self.junction_boxes, |p1, p2| {
             p1.0.distance(p1.1).total_cmp(&p2.0.distance(p2.1))
         });
         let mut circuits = DisjointSet::new(self.junction_boxes.len());
-        let mut last_connection = *cartesian.first().unwrap();
+        let mut last_connection = *cartesian.first().ok_or(AoCError::LogicError(
+            "at least two junction boxes are required".to_string(),
+        ))?;
         for (j1, j2) in cartesian {
             circuits.merge(j1.id, j2.id)?;
             if circuits.num_sets == 1 {
@@ -150,7 +162,12 @@ impl Day for Day8 {
             }
         }
 
-        println!("last connection: {:?}", last_connection);
-        Ok(last_connection.0.pos.x * last_connection.1.pos.x)
+        last_connection
+            .0
+            .pos
+            .x
+            .checked_mul(last_connection.1.pos.x)
+            .ok_or(AoCError::LogicError("result overflows".to_string()))
+            .map(Into::into)
     }
 }